@@ -0,0 +1,345 @@
+//! Hand-maintained `ExtendLookupTable` builder with an optional payer.
+//!
+//! [`ExtendLookupTableOptionalPayerBuilder`] is an alternative to the
+//! generated [`crate::instructions::ExtendLookupTableBuilder`], which always
+//! requires a payer. The program only reads the payer and system program
+//! accounts when the table needs more lamports to stay rent exempt. Tables
+//! that were funded ahead of time can be extended with just the table and
+//! its authority.
+//!
+//! Given an [`ExtendRentContext`] fetched ahead of time, the builder makes
+//! that decision itself and only includes the payer accounts when the
+//! extension needs more lamports.
+
+use {
+    crate::instructions::ExtendLookupTableInstructionData,
+    borsh::BorshSerialize,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::{Pubkey, PUBKEY_BYTES},
+        rent::Rent,
+    },
+};
+
+/// Pre-fetched state used to decide whether extending a lookup table needs
+/// a payer.
+#[derive(Clone, Debug)]
+pub struct ExtendRentContext {
+    /// The cluster's rent parameters, from the `Rent` sysvar.
+    pub rent: Rent,
+    /// The lookup table account's current lamport balance.
+    pub table_lamports: u64,
+    /// The lookup table account's current data length.
+    pub table_data_len: usize,
+}
+
+impl ExtendRentContext {
+    /// Returns the lamports the program will transfer from the payer when
+    /// `num_new_addresses` addresses are appended to the table.
+    ///
+    /// Mirrors the program, which tops the table up to the rent-exempt
+    /// minimum for its new size.
+    pub fn required_lamports(&self, num_new_addresses: usize) -> u64 {
+        let new_table_data_len = self
+            .table_data_len
+            .saturating_add(num_new_addresses.saturating_mul(PUBKEY_BYTES));
+        self.rent
+            .minimum_balance(new_table_data_len)
+            .max(1)
+            .saturating_sub(self.table_lamports)
+    }
+
+    /// Returns `true` if appending `num_new_addresses` addresses requires a
+    /// payer.
+    pub fn requires_payer(&self, num_new_addresses: usize) -> bool {
+        self.required_lamports(num_new_addresses) > 0
+    }
+}
+
+/// Instruction builder for `ExtendLookupTable` where the payer and system
+/// program are optional.
+///
+/// ### Accounts:
+///
+///   0. `[writable]` address
+///   1. `[signer]` authority
+///   2. `[writable, signer, optional]` payer
+///   3. `[optional]` system_program (default to
+///      `11111111111111111111111111111111`, only included with a payer)
+///
+/// When a rent context is set, the payer and system program are only
+/// included if the extension needs more lamports.
+#[derive(Clone, Debug, Default)]
+pub struct ExtendLookupTableOptionalPayerBuilder {
+    address: Option<Pubkey>,
+    authority: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    system_program: Option<Pubkey>,
+    new_addresses: Option<Vec<Pubkey>>,
+    rent_context: Option<ExtendRentContext>,
+    __remaining_accounts: Vec<AccountMeta>,
+}
+
+impl ExtendLookupTableOptionalPayerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    #[inline(always)]
+    pub fn address(&mut self, address: Pubkey) -> &mut Self {
+        self.address = Some(address);
+        self
+    }
+    #[inline(always)]
+    pub fn authority(&mut self, authority: Pubkey) -> &mut Self {
+        self.authority = Some(authority);
+        self
+    }
+    /// `[optional account]`
+    #[inline(always)]
+    pub fn payer(&mut self, payer: Option<Pubkey>) -> &mut Self {
+        self.payer = payer;
+        self
+    }
+    /// `[optional account, default to '11111111111111111111111111111111']`
+    ///
+    /// Ignored unless a payer is set, since the program reads the system
+    /// program right after the payer.
+    #[inline(always)]
+    pub fn system_program(&mut self, system_program: Option<Pubkey>) -> &mut Self {
+        self.system_program = system_program;
+        self
+    }
+    #[inline(always)]
+    pub fn new_addresses(&mut self, new_addresses: Vec<Pubkey>) -> &mut Self {
+        self.new_addresses = Some(new_addresses);
+        self
+    }
+    /// Pre-fetched rent state of the lookup table. When set, the payer
+    /// accounts are omitted if the table already holds enough lamports for
+    /// the new addresses, and building the instruction panics if they are
+    /// needed but no payer is set. When unset, a configured payer is always
+    /// included.
+    #[inline(always)]
+    pub fn rent_context(&mut self, rent_context: Option<ExtendRentContext>) -> &mut Self {
+        self.rent_context = rent_context;
+        self
+    }
+    /// Add an additional account to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_account(&mut self, account: AccountMeta) -> &mut Self {
+        self.__remaining_accounts.push(account);
+        self
+    }
+    /// Add additional accounts to the instruction.
+    #[inline(always)]
+    pub fn add_remaining_accounts(&mut self, accounts: &[AccountMeta]) -> &mut Self {
+        self.__remaining_accounts.extend_from_slice(accounts);
+        self
+    }
+    pub fn instruction(&self) -> Instruction {
+        let new_addresses = self
+            .new_addresses
+            .clone()
+            .expect("new_addresses is not set");
+        let payer = match &self.rent_context {
+            Some(context) if context.requires_payer(new_addresses.len()) => {
+                Some(self.payer.expect("payer is required to fund the extension"))
+            }
+            Some(_) => None,
+            None => self.payer,
+        };
+
+        let mut accounts = Vec::with_capacity(4 + self.__remaining_accounts.len());
+        accounts.push(AccountMeta::new(
+            self.address.expect("address is not set"),
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(
+            self.authority.expect("authority is not set"),
+            true,
+        ));
+        if let Some(payer) = payer {
+            accounts.push(AccountMeta::new(payer, true));
+            accounts.push(AccountMeta::new_readonly(
+                self.system_program
+                    .unwrap_or(solana_program::system_program::ID),
+                false,
+            ));
+        }
+        accounts.extend_from_slice(&self.__remaining_accounts);
+
+        let mut data = ExtendLookupTableInstructionData::new()
+            .try_to_vec()
+            .unwrap();
+        // `U64PrefixVec` can't be built from a `Vec`, so write its `u64`
+        // length prefix and the addresses directly.
+        data.extend_from_slice(&(new_addresses.len() as u64).to_le_bytes());
+        for address in &new_addresses {
+            data.extend_from_slice(address.as_ref());
+        }
+
+        Instruction {
+            program_id: crate::ID,
+            accounts,
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+    fn expected_data(new_addresses: &[Pubkey]) -> Vec<u8> {
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&(new_addresses.len() as u64).to_le_bytes());
+        for address in new_addresses {
+            data.extend_from_slice(address.as_ref());
+        }
+        data
+    }
+
+    #[test]
+    fn test_extend_with_payer() {
+        let address = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let new_addresses = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let instruction = ExtendLookupTableOptionalPayerBuilder::new()
+            .address(address)
+            .authority(authority)
+            .payer(Some(payer))
+            .new_addresses(new_addresses.clone())
+            .instruction();
+
+        assert_eq!(instruction.program_id, crate::ID);
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                AccountMeta::new(address, false),
+                AccountMeta::new_readonly(authority, true),
+                AccountMeta::new(payer, true),
+                AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            ]
+        );
+        assert_eq!(instruction.data, expected_data(&new_addresses));
+    }
+
+    #[test]
+    fn test_extend_without_payer() {
+        let address = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let new_addresses = vec![Pubkey::new_unique()];
+
+        let instruction = ExtendLookupTableOptionalPayerBuilder::new()
+            .address(address)
+            .authority(authority)
+            .system_program(Some(solana_program::system_program::ID))
+            .new_addresses(new_addresses.clone())
+            .instruction();
+
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                AccountMeta::new(address, false),
+                AccountMeta::new_readonly(authority, true),
+            ]
+        );
+        assert_eq!(instruction.data, expected_data(&new_addresses));
+    }
+
+    fn rent_context(table_lamports: u64) -> ExtendRentContext {
+        ExtendRentContext {
+            rent: Rent::default(),
+            table_lamports,
+            table_data_len: LOOKUP_TABLE_META_SIZE + PUBKEY_BYTES,
+        }
+    }
+
+    #[test]
+    fn test_extend_with_rent_context_requiring_payer() {
+        let address = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let new_addresses = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+
+        // Only funded for the current size.
+        let context =
+            rent_context(Rent::default().minimum_balance(LOOKUP_TABLE_META_SIZE + PUBKEY_BYTES));
+        assert_eq!(
+            context.required_lamports(2),
+            Rent::default().minimum_balance(LOOKUP_TABLE_META_SIZE + 3 * PUBKEY_BYTES)
+                - Rent::default().minimum_balance(LOOKUP_TABLE_META_SIZE + PUBKEY_BYTES)
+        );
+
+        let instruction = ExtendLookupTableOptionalPayerBuilder::new()
+            .address(address)
+            .authority(authority)
+            .payer(Some(payer))
+            .rent_context(Some(context))
+            .new_addresses(new_addresses.clone())
+            .instruction();
+
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                AccountMeta::new(address, false),
+                AccountMeta::new_readonly(authority, true),
+                AccountMeta::new(payer, true),
+                AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            ]
+        );
+        assert_eq!(instruction.data, expected_data(&new_addresses));
+    }
+
+    #[test]
+    #[should_panic(expected = "payer is required to fund the extension")]
+    fn test_extend_with_rent_context_requiring_missing_payer() {
+        // Only funded for the current size.
+        let context =
+            rent_context(Rent::default().minimum_balance(LOOKUP_TABLE_META_SIZE + PUBKEY_BYTES));
+        assert!(context.requires_payer(1));
+
+        ExtendLookupTableOptionalPayerBuilder::new()
+            .address(Pubkey::new_unique())
+            .authority(Pubkey::new_unique())
+            .rent_context(Some(context))
+            .new_addresses(vec![Pubkey::new_unique()])
+            .instruction();
+    }
+
+    #[test]
+    fn test_extend_with_rent_context_prepaid() {
+        let address = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let new_addresses = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+
+        // Prepaid for the size after the extension.
+        let context = rent_context(
+            Rent::default().minimum_balance(LOOKUP_TABLE_META_SIZE + 3 * PUBKEY_BYTES),
+        );
+        assert!(!context.requires_payer(2));
+        assert!(context.requires_payer(3));
+
+        let instruction = ExtendLookupTableOptionalPayerBuilder::new()
+            .address(address)
+            .authority(authority)
+            .payer(Some(payer))
+            .rent_context(Some(context))
+            .new_addresses(new_addresses.clone())
+            .instruction();
+
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                AccountMeta::new(address, false),
+                AccountMeta::new_readonly(authority, true),
+            ]
+        );
+        assert_eq!(instruction.data, expected_data(&new_addresses));
+    }
+}
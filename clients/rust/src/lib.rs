@@ -1,3 +1,4 @@
+pub mod extend;
 mod generated;
 
 pub use generated::{programs::ADDRESS_LOOKUP_TABLE_ID as ID, *};
@@ -0,0 +1,28 @@
+use {
+    solana_address_lookup_table_client::{instructions::CloseLookupTableBuilder, ID},
+    solana_program::{instruction::AccountMeta, pubkey::Pubkey},
+};
+
+#[test]
+fn test_close_lookup_table_builder() {
+    let address = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+
+    let instruction = CloseLookupTableBuilder::new()
+        .address(address)
+        .authority(authority)
+        .recipient(recipient)
+        .instruction();
+
+    assert_eq!(instruction.program_id, ID);
+    assert_eq!(
+        instruction.accounts,
+        vec![
+            AccountMeta::new(address, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(recipient, false),
+        ]
+    );
+    assert_eq!(instruction.data, 4u32.to_le_bytes().to_vec());
+}
@@ -4,24 +4,33 @@ mod setup;
 
 use {
     crate::setup::{
-        close_lookup_table, create_lookup_table, deactivate_lookup_table, extend_lookup_table,
-        freeze_lookup_table, TEST_CLOCK_SLOT,
+        close_lookup_table, close_lookup_table_deactivated_at, create_lookup_table,
+        deactivate_lookup_table, extend_lookup_table, freeze_lookup_table, TEST_CLOCK_SLOT,
     },
     mollusk_svm::Mollusk,
     mollusk_svm_bencher::MolluskComputeUnitBencher,
+    solana_sdk::slot_hashes::MAX_ENTRIES,
 };
 
-fn main() {
-    std::env::set_var("SBF_OUT_DIR", "../target/deploy");
+// `SlotHashes` holds the `MAX_ENTRIES` slots preceding the clock slot.
+const NEWEST_SLOT_HASH: u64 = TEST_CLOCK_SLOT - 1;
+const MIDDLE_SLOT_HASH: u64 = TEST_CLOCK_SLOT - (MAX_ENTRIES as u64 / 2);
+const OLDEST_SLOT_HASH: u64 = TEST_CLOCK_SLOT - MAX_ENTRIES as u64;
+const PAST_SLOT_HASHES: u64 = OLDEST_SLOT_HASH - 1;
 
+fn mollusk() -> Mollusk {
     let mut mollusk = Mollusk::new(
         &solana_address_lookup_table_program::id(),
         "solana_address_lookup_table_program",
     );
-
     mollusk.warp_to_slot(TEST_CLOCK_SLOT);
+    mollusk
+}
 
-    MolluskComputeUnitBencher::new(mollusk)
+fn main() {
+    std::env::set_var("SBF_OUT_DIR", "../target/deploy");
+
+    MolluskComputeUnitBencher::new(mollusk())
         .bench(create_lookup_table().bench())
         .bench(freeze_lookup_table().bench())
         .bench(extend_lookup_table(0, 1).bench())
@@ -43,7 +52,21 @@ fn main() {
         .bench(extend_lookup_table(255, 256).bench())
         .bench(deactivate_lookup_table().bench())
         .bench(close_lookup_table().bench())
+        .bench(close_lookup_table_deactivated_at("past_window", PAST_SLOT_HASHES).bench())
         .must_pass(true)
         .out_dir("./benches")
         .execute();
+
+    // Tables deactivated within the slot hash window are still cooling down,
+    // so these closes are rejected, but only after the deactivation slot has
+    // been located in `SlotHashes`.
+    let cooldown_out_dir = "./benches/cooldown";
+    std::fs::create_dir_all(cooldown_out_dir).unwrap();
+    MolluskComputeUnitBencher::new(mollusk())
+        .bench(close_lookup_table_deactivated_at("window_start", NEWEST_SLOT_HASH).bench())
+        .bench(close_lookup_table_deactivated_at("window_middle", MIDDLE_SLOT_HASH).bench())
+        .bench(close_lookup_table_deactivated_at("window_end", OLDEST_SLOT_HASH).bench())
+        .must_pass(false)
+        .out_dir(cooldown_out_dir)
+        .execute();
 }
@@ -0,0 +1,46 @@
+//! Address Lookup Table deserialization benchmark across table sizes.
+
+use {
+    solana_address_lookup_table_program::state::{AddressLookupTable, LookupTableMeta},
+    solana_sdk::pubkey::Pubkey,
+    std::{borrow::Cow, hint::black_box, time::Instant},
+};
+
+const ITERATIONS: u32 = 1_000_000;
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    // Warm up.
+    for _ in 0..ITERATIONS / 10 {
+        f();
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{name:<24} {:>8.2} ns/iter",
+        elapsed.as_nanos() as f64 / ITERATIONS as f64
+    );
+}
+
+fn main() {
+    for num_addresses in [0, 1, 38, 128, 256] {
+        let lookup_table = AddressLookupTable {
+            meta: LookupTableMeta {
+                last_extended_slot: 1,
+                ..LookupTableMeta::new(Pubkey::new_unique())
+            },
+            addresses: Cow::Owned(vec![Pubkey::new_unique(); num_addresses]),
+        };
+        let data = lookup_table.serialize().unwrap();
+
+        bench(&format!("{num_addresses} addresses"), || {
+            let lookup_table = AddressLookupTable::deserialize(black_box(&data)).unwrap();
+            black_box(lookup_table.meta.authority);
+            black_box(lookup_table.addresses.len());
+        });
+    }
+}
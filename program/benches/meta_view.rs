@@ -0,0 +1,53 @@
+//! Address Lookup Table metadata deserialization benchmark, comparing the
+//! `bincode` path against the zero-copy `LookupTableMetaView`.
+
+use {
+    solana_address_lookup_table_program::state::{AddressLookupTable, LookupTableMeta},
+    solana_sdk::pubkey::Pubkey,
+    std::{borrow::Cow, hint::black_box, time::Instant},
+};
+
+const ITERATIONS: u32 = 1_000_000;
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    // Warm up.
+    for _ in 0..ITERATIONS / 10 {
+        f();
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{name:<24} {:>8.2} ns/iter",
+        elapsed.as_nanos() as f64 / ITERATIONS as f64
+    );
+}
+
+fn main() {
+    let lookup_table = AddressLookupTable {
+        meta: LookupTableMeta {
+            last_extended_slot: 1,
+            ..LookupTableMeta::new(Pubkey::new_unique())
+        },
+        addresses: Cow::Owned(vec![Pubkey::new_unique(); 256]),
+    };
+    let data = lookup_table.serialize().unwrap();
+
+    bench("bincode deserialize", || {
+        let meta = AddressLookupTable::deserialize(black_box(&data))
+            .unwrap()
+            .meta;
+        black_box(meta.deactivation_slot);
+        black_box(meta.authority);
+    });
+
+    bench("meta view", || {
+        let view = AddressLookupTable::deserialize_meta_view(black_box(&data)).unwrap();
+        black_box(view.deactivation_slot());
+        black_box(view.authority());
+    });
+}
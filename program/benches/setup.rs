@@ -9,13 +9,11 @@ use {
             extend_lookup_table as extend_lookup_table_ix,
             freeze_lookup_table as freeze_lookup_table_ix,
         },
-        state::{AddressLookupTable, LookupTableMeta},
+        test_utils,
     },
     solana_sdk::{
-        account::AccountSharedData, instruction::Instruction, pubkey::Pubkey, rent::Rent,
-        system_program,
+        account::AccountSharedData, instruction::Instruction, pubkey::Pubkey, system_program,
     },
-    std::borrow::Cow,
 };
 
 pub const TEST_CLOCK_SLOT: u64 = 100_000;
@@ -39,28 +37,20 @@ fn lookup_table_account(
     num_keys: usize,
     deactivated: bool,
 ) -> AccountSharedData {
-    let state = {
-        let mut addresses = Vec::with_capacity(num_keys);
-        addresses.resize_with(num_keys, Pubkey::new_unique);
-        AddressLookupTable {
-            meta: LookupTableMeta {
-                authority: Some(*authority),
-                deactivation_slot: if deactivated { 1 } else { u64::MAX },
-                ..LookupTableMeta::default()
-            },
-            addresses: Cow::Owned(addresses),
-        }
-    };
-    let data = state.serialize_for_tests().unwrap();
-    let data_len = data.len();
-    let lamports = Rent::default().minimum_balance(data_len);
-    let mut account = AccountSharedData::new(
-        lamports,
-        data_len,
-        &solana_address_lookup_table_program::id(),
-    );
-    account.set_data_from_slice(&data);
-    account
+    let deactivation_slot = if deactivated { 1 } else { u64::MAX };
+    lookup_table_account_with_deactivation_slot(authority, num_keys, deactivation_slot)
+}
+
+fn lookup_table_account_with_deactivation_slot(
+    authority: &Pubkey,
+    num_keys: usize,
+    deactivation_slot: u64,
+) -> AccountSharedData {
+    test_utils::lookup_table_account(test_utils::new_address_lookup_table(
+        Some(*authority),
+        num_keys,
+        deactivation_slot,
+    ))
 }
 
 pub fn create_lookup_table() -> BenchContext {
@@ -167,3 +157,29 @@ pub fn close_lookup_table() -> BenchContext {
         accounts,
     }
 }
+
+/// Close a table deactivated at `deactivation_slot`, to measure how the
+/// deactivation slot's position in the `SlotHashes` sysvar affects the cost
+/// of the cooldown check.
+pub fn close_lookup_table_deactivated_at(label: &str, deactivation_slot: u64) -> BenchContext {
+    let lookup_table = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+
+    let instruction = close_lookup_table_ix(lookup_table, authority, recipient);
+
+    let accounts = vec![
+        (
+            lookup_table,
+            lookup_table_account_with_deactivation_slot(&authority, 1, deactivation_slot),
+        ),
+        (authority, AccountSharedData::default()),
+        (recipient, AccountSharedData::default()),
+    ];
+
+    BenchContext {
+        label: format!("close_lookup_table_{}", label),
+        instruction,
+        accounts,
+    }
+}
@@ -0,0 +1,591 @@
+//! Off-chain client for interacting with address lookup tables over RPC.
+//!
+//! Enabled with the `client` feature, which pulls in the Solana RPC client
+//! and SDK crates. It is not intended for on-chain use.
+
+use {
+    crate::{
+        instruction::{create_lookup_table, extend_lookup_table_chunked},
+        state::{
+            closable_refund_lamports, rent_for_addresses, AddressLookupTable, LookupTableMeta,
+        },
+    },
+    solana_program::{
+        clock::Slot, hash::Hash, program_error::ProgramError, pubkey::Pubkey, rent::Rent,
+        system_instruction,
+    },
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_rpc_client_api::{
+        client_error::Error as RpcClientError, request::MAX_MULTIPLE_ACCOUNTS,
+    },
+    solana_sdk::{
+        account::Account, commitment_config::CommitmentConfig, signature::Signature,
+        signer::Signer, transaction::Transaction,
+    },
+    thiserror::Error,
+};
+
+/// Errors returned by the `LookupTableClient`.
+#[derive(Error, Debug)]
+pub enum LookupTableClientError {
+    /// The RPC request failed.
+    #[error("RPC request failed: {0}")]
+    Rpc(#[source] Box<RpcClientError>),
+    /// The lookup table account does not exist.
+    #[error("Lookup table account {0} not found")]
+    AccountNotFound(Pubkey),
+    /// The account is not owned by the Address Lookup Table program.
+    #[error("Account {0} is not owned by the Address Lookup Table program")]
+    InvalidAccountOwner(Pubkey),
+    /// The account data could not be deserialized as a lookup table.
+    #[error("Failed to deserialize lookup table: {0}")]
+    InvalidAccountData(#[from] ProgramError),
+}
+
+impl From<RpcClientError> for LookupTableClientError {
+    fn from(error: RpcClientError) -> Self {
+        // Boxed since the RPC error is much larger than the other variants.
+        Self::Rpc(Box::new(error))
+    }
+}
+
+/// A thin wrapper over an `RpcClient` for fetching, creating, and extending
+/// address lookup tables.
+pub struct LookupTableClient {
+    rpc_client: RpcClient,
+}
+
+impl LookupTableClient {
+    /// Create a new client which sends requests through the provided
+    /// `RpcClient`, using its configured commitment.
+    pub fn new(rpc_client: RpcClient) -> Self {
+        Self { rpc_client }
+    }
+
+    /// The underlying `RpcClient`.
+    pub fn rpc_client(&self) -> &RpcClient {
+        &self.rpc_client
+    }
+
+    /// Fetch and deserialize an address lookup table.
+    pub async fn fetch_table(
+        &self,
+        lookup_table_address: &Pubkey,
+    ) -> Result<AddressLookupTable<'static>, LookupTableClientError> {
+        let account = self.fetch_table_account(lookup_table_address).await?;
+        Ok(AddressLookupTable::deserialize(&account.data)?.into_owned())
+    }
+
+    /// Fetch only the authority of an address lookup table, or `None` if
+    /// the table is frozen.
+    ///
+    /// The authority is read directly from the account data, without
+    /// deserializing the table's addresses, which keeps bulk scans cheap.
+    pub async fn fetch_authority(
+        &self,
+        lookup_table_address: &Pubkey,
+    ) -> Result<Option<Pubkey>, LookupTableClientError> {
+        let account = self.fetch_table_account(lookup_table_address).await?;
+        Ok(LookupTableMeta::read_authority(&account.data)?)
+    }
+
+    /// Fetch the lamports which closing an address lookup table would
+    /// refund to the recipient. See [`closable_refund_lamports`].
+    ///
+    /// This does not check whether the table can be closed yet; a table
+    /// must be deactivated and finish its cooldown before it can be closed.
+    pub async fn fetch_closable_refund(
+        &self,
+        lookup_table_address: &Pubkey,
+    ) -> Result<u64, LookupTableClientError> {
+        let account = self.fetch_table_account(lookup_table_address).await?;
+        Ok(closable_refund_lamports(account.lamports))
+    }
+
+    async fn fetch_table_account(
+        &self,
+        lookup_table_address: &Pubkey,
+    ) -> Result<Account, LookupTableClientError> {
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(lookup_table_address, self.rpc_client.commitment())
+            .await?
+            .value
+            .ok_or(LookupTableClientError::AccountNotFound(
+                *lookup_table_address,
+            ))?;
+
+        if account.owner != crate::id() {
+            return Err(LookupTableClientError::InvalidAccountOwner(
+                *lookup_table_address,
+            ));
+        }
+
+        Ok(account)
+    }
+
+    /// Fetch and deserialize many address lookup tables, requesting up to
+    /// `MAX_MULTIPLE_ACCOUNTS` accounts per RPC request.
+    ///
+    /// The returned tables are in the same order as the provided addresses.
+    /// Missing accounts, and accounts which do not hold a valid lookup
+    /// table, are returned as `None`.
+    pub async fn fetch_tables(
+        &self,
+        lookup_table_addresses: &[Pubkey],
+    ) -> Result<Vec<Option<AddressLookupTable<'static>>>, LookupTableClientError> {
+        let mut lookup_tables = Vec::with_capacity(lookup_table_addresses.len());
+        for addresses in lookup_table_addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let accounts = self
+                .rpc_client
+                .get_multiple_accounts_with_commitment(addresses, self.rpc_client.commitment())
+                .await?
+                .value;
+            lookup_tables.extend(accounts.into_iter().map(|account| {
+                account
+                    .filter(|account| account.owner == crate::id())
+                    .and_then(|account| {
+                        AddressLookupTable::deserialize(&account.data)
+                            .map(AddressLookupTable::into_owned)
+                            .ok()
+                    })
+            }));
+        }
+        Ok(lookup_tables)
+    }
+
+    /// Create a new address lookup table, returning its address.
+    ///
+    /// The payer also pre-funds the table with enough lamports to be rent
+    /// exempt once it holds `prefund_addresses` addresses, so that it can
+    /// later be extended without a payer.
+    pub async fn create_and_fund(
+        &self,
+        authority: &dyn Signer,
+        payer: &dyn Signer,
+        prefund_addresses: usize,
+    ) -> Result<Pubkey, LookupTableClientError> {
+        // The derivation slot must be present in the `SlotHashes` sysvar
+        // when the transaction is processed.
+        let recent_slot = self
+            .rpc_client
+            .get_slot_with_commitment(CommitmentConfig::finalized())
+            .await?;
+
+        let recent_blockhash = self.rpc_client.get_latest_blockhash().await?;
+        let (transaction, lookup_table_address) = create_and_fund_transaction(
+            &authority.pubkey(),
+            payer,
+            recent_slot,
+            prefund_addresses,
+            recent_blockhash,
+        );
+        self.rpc_client
+            .send_and_confirm_transaction(&transaction)
+            .await?;
+
+        Ok(lookup_table_address)
+    }
+
+    /// Extend an address lookup table with new addresses, splitting them
+    /// across as many transactions as required. Transactions are sent and
+    /// confirmed in order, and their signatures are returned.
+    pub async fn extend(
+        &self,
+        lookup_table_address: &Pubkey,
+        authority: &dyn Signer,
+        payer: &dyn Signer,
+        new_addresses: Vec<Pubkey>,
+    ) -> Result<Vec<Signature>, LookupTableClientError> {
+        let recent_blockhash = self.rpc_client.get_latest_blockhash().await?;
+        let transactions = extend_transactions(
+            lookup_table_address,
+            authority,
+            payer,
+            new_addresses,
+            recent_blockhash,
+        );
+
+        let mut signatures = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            let signature = self
+                .rpc_client
+                .send_and_confirm_transaction(&transaction)
+                .await?;
+            signatures.push(signature);
+        }
+        Ok(signatures)
+    }
+}
+
+/// Build the signed transaction sent by `LookupTableClient::create_and_fund`,
+/// returning it along with the new table's address.
+fn create_and_fund_transaction(
+    authority: &Pubkey,
+    payer: &dyn Signer,
+    recent_slot: Slot,
+    prefund_addresses: usize,
+    recent_blockhash: Hash,
+) -> (Transaction, Pubkey) {
+    let (create_instruction, lookup_table_address) =
+        create_lookup_table(*authority, payer.pubkey(), recent_slot);
+
+    let mut instructions = vec![create_instruction];
+
+    // All clusters use the default rent configuration.
+    let rent = Rent::default();
+    let prefund_lamports =
+        rent_for_addresses(&rent, prefund_addresses).saturating_sub(rent_for_addresses(&rent, 0));
+    if prefund_lamports > 0 {
+        instructions.push(system_instruction::transfer(
+            &payer.pubkey(),
+            &lookup_table_address,
+            prefund_lamports,
+        ));
+    }
+
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    (transaction, lookup_table_address)
+}
+
+/// Build the signed transactions sent by `LookupTableClient::extend`, one
+/// per chunk of addresses, each signed by both the payer and the authority.
+fn extend_transactions(
+    lookup_table_address: &Pubkey,
+    authority: &dyn Signer,
+    payer: &dyn Signer,
+    new_addresses: Vec<Pubkey>,
+    recent_blockhash: Hash,
+) -> Vec<Transaction> {
+    extend_lookup_table_chunked(
+        *lookup_table_address,
+        authority.pubkey(),
+        Some(payer.pubkey()),
+        new_addresses,
+    )
+    .into_iter()
+    .map(|instruction| {
+        Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer, authority],
+            recent_blockhash,
+        )
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::state::LOOKUP_TABLE_META_SIZE,
+        base64::{prelude::BASE64_STANDARD, Engine},
+        solana_program::pubkey::PUBKEY_BYTES,
+        solana_rpc_client::mock_sender::Mocks,
+        solana_rpc_client_api::request::RpcRequest,
+        solana_sdk::signature::Keypair,
+        std::borrow::Cow,
+    };
+
+    fn account_json(account: Option<(Pubkey, Vec<u8>)>) -> serde_json::Value {
+        account
+            .map(|(owner, data)| {
+                serde_json::json!({
+                    "data": [BASE64_STANDARD.encode(&data), "base64"],
+                    "executable": false,
+                    "lamports": Rent::default().minimum_balance(data.len()),
+                    "owner": owner.to_string(),
+                    "rentEpoch": 0,
+                    "space": data.len(),
+                })
+            })
+            .unwrap_or(serde_json::Value::Null)
+    }
+
+    fn mock_client_with(request: RpcRequest, value: serde_json::Value) -> LookupTableClient {
+        let mut mocks = Mocks::default();
+        mocks.insert(
+            request,
+            serde_json::json!({
+                "context": { "slot": 1 },
+                "value": value,
+            }),
+        );
+        LookupTableClient::new(RpcClient::new_mock_with_mocks(
+            "succeeds".to_string(),
+            mocks,
+        ))
+    }
+
+    fn mock_client(account: Option<(Pubkey, Vec<u8>)>) -> LookupTableClient {
+        mock_client_with(RpcRequest::GetAccountInfo, account_json(account))
+    }
+
+    #[tokio::test]
+    async fn test_fetch_table() {
+        let lookup_table = AddressLookupTable {
+            meta: LookupTableMeta::new(Pubkey::new_unique()),
+            addresses: Cow::Owned(vec![Pubkey::new_unique(); 10]),
+        };
+        let client = mock_client(Some((crate::id(), lookup_table.serialize().unwrap())));
+
+        assert_eq!(
+            client.fetch_table(&Pubkey::new_unique()).await.unwrap(),
+            lookup_table,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_table_not_found() {
+        let client = mock_client(None);
+        let lookup_table_address = Pubkey::new_unique();
+
+        assert!(matches!(
+            client.fetch_table(&lookup_table_address).await,
+            Err(LookupTableClientError::AccountNotFound(address))
+                if address == lookup_table_address
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_table_invalid_owner() {
+        let lookup_table = AddressLookupTable {
+            meta: LookupTableMeta::new(Pubkey::new_unique()),
+            addresses: Cow::Owned(vec![]),
+        };
+        let client = mock_client(Some((
+            Pubkey::new_unique(),
+            lookup_table.serialize().unwrap(),
+        )));
+
+        assert!(matches!(
+            client.fetch_table(&Pubkey::new_unique()).await,
+            Err(LookupTableClientError::InvalidAccountOwner(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_table_invalid_data() {
+        let client = mock_client(Some((crate::id(), vec![0; 4])));
+
+        assert!(matches!(
+            client.fetch_table(&Pubkey::new_unique()).await,
+            Err(LookupTableClientError::InvalidAccountData(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_authority() {
+        let authority = Pubkey::new_unique();
+        let lookup_table = AddressLookupTable {
+            meta: LookupTableMeta::new(authority),
+            addresses: Cow::Owned(vec![Pubkey::new_unique(); 10]),
+        };
+        // Trailing data that is not a whole address would fail full
+        // deserialization, but is never read when fetching the authority.
+        let mut lookup_table_data = lookup_table.serialize().unwrap();
+        lookup_table_data.push(0);
+        let client = mock_client(Some((crate::id(), lookup_table_data)));
+
+        assert_eq!(
+            client.fetch_authority(&Pubkey::new_unique()).await.unwrap(),
+            Some(authority),
+        );
+
+        // Frozen table.
+        let lookup_table = AddressLookupTable::from_addresses(None, vec![Pubkey::new_unique()]);
+        let client = mock_client(Some((crate::id(), lookup_table.serialize().unwrap())));
+        assert_eq!(
+            client.fetch_authority(&Pubkey::new_unique()).await.unwrap(),
+            None,
+        );
+
+        // Invalid owner.
+        let client = mock_client(Some((Pubkey::new_unique(), vec![0; 56])));
+        assert!(matches!(
+            client.fetch_authority(&Pubkey::new_unique()).await,
+            Err(LookupTableClientError::InvalidAccountOwner(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_tables() {
+        let lookup_table = AddressLookupTable {
+            meta: LookupTableMeta::new(Pubkey::new_unique()),
+            addresses: Cow::Owned(vec![Pubkey::new_unique(); 10]),
+        };
+        let lookup_table_data = lookup_table.serialize().unwrap();
+        let client = mock_client_with(
+            RpcRequest::GetMultipleAccounts,
+            serde_json::json!([
+                account_json(Some((crate::id(), lookup_table_data.clone()))),
+                account_json(None),
+                account_json(Some((Pubkey::new_unique(), lookup_table_data))),
+                account_json(Some((crate::id(), vec![0; 4]))),
+            ]),
+        );
+
+        let lookup_table_addresses = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        assert_eq!(
+            client.fetch_tables(&lookup_table_addresses).await.unwrap(),
+            vec![Some(lookup_table), None, None, None],
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_and_fund() {
+        let client = mock_client(None);
+        let authority = Keypair::new();
+        let payer = Keypair::new();
+
+        client
+            .create_and_fund(&authority, &payer, 10)
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn test_create_and_fund_transaction() {
+        let authority = Pubkey::new_unique();
+        let payer = Keypair::new();
+        let recent_slot = 42;
+        let recent_blockhash = Hash::new_unique();
+
+        let (create_instruction, expected_address) =
+            create_lookup_table(authority, payer.pubkey(), recent_slot);
+        let prefund_lamports = Rent::default()
+            .minimum_balance(LOOKUP_TABLE_META_SIZE + 10 * PUBKEY_BYTES)
+            - Rent::default().minimum_balance(LOOKUP_TABLE_META_SIZE);
+
+        let (transaction, lookup_table_address) =
+            create_and_fund_transaction(&authority, &payer, recent_slot, 10, recent_blockhash);
+
+        assert_eq!(lookup_table_address, expected_address);
+        assert_eq!(
+            transaction,
+            Transaction::new_signed_with_payer(
+                &[
+                    create_instruction.clone(),
+                    system_instruction::transfer(
+                        &payer.pubkey(),
+                        &lookup_table_address,
+                        prefund_lamports,
+                    ),
+                ],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            ),
+        );
+        assert!(transaction.verify().is_ok());
+
+        // No prefund transfer when no addresses are prefunded.
+        let (transaction, _) =
+            create_and_fund_transaction(&authority, &payer, recent_slot, 0, recent_blockhash);
+        assert_eq!(
+            transaction,
+            Transaction::new_signed_with_payer(
+                &[create_instruction],
+                Some(&payer.pubkey()),
+                &[&payer],
+                recent_blockhash,
+            ),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extend() {
+        let client = mock_client(None);
+        let authority = Keypair::new();
+        let payer = Keypair::new();
+
+        let mut new_addresses = Vec::with_capacity(50);
+        new_addresses.resize_with(50, Pubkey::new_unique);
+
+        let signatures = client
+            .extend(&Pubkey::new_unique(), &authority, &payer, new_addresses)
+            .await
+            .unwrap();
+
+        // 50 addresses are split across two transactions.
+        assert_eq!(signatures.len(), 2);
+    }
+
+    #[test]
+    fn test_extend_transactions() {
+        let lookup_table_address = Pubkey::new_unique();
+        let authority = Keypair::new();
+        let payer = Keypair::new();
+        let recent_blockhash = Hash::new_unique();
+
+        let mut new_addresses = Vec::with_capacity(50);
+        new_addresses.resize_with(50, Pubkey::new_unique);
+
+        let transactions = extend_transactions(
+            &lookup_table_address,
+            &authority,
+            &payer,
+            new_addresses.clone(),
+            recent_blockhash,
+        );
+
+        let chunks = extend_lookup_table_chunked(
+            lookup_table_address,
+            authority.pubkey(),
+            Some(payer.pubkey()),
+            new_addresses,
+        );
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(chunks.len(), 2);
+
+        for (transaction, instruction) in transactions.iter().zip(chunks) {
+            // Each chunk is signed by both the payer and the authority.
+            assert_eq!(transaction.message.header.num_required_signatures, 2);
+            assert_eq!(
+                transaction.message.account_keys[..2],
+                [payer.pubkey(), authority.pubkey()],
+            );
+            assert!(transaction.verify().is_ok());
+
+            assert_eq!(
+                *transaction,
+                Transaction::new_signed_with_payer(
+                    &[instruction],
+                    Some(&payer.pubkey()),
+                    &[&payer, &authority],
+                    recent_blockhash,
+                ),
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_closable_refund() {
+        let lookup_table = AddressLookupTable {
+            meta: LookupTableMeta::new(Pubkey::new_unique()),
+            addresses: Cow::Owned(vec![Pubkey::new_unique(); 10]),
+        };
+        let lookup_table_data = lookup_table.serialize().unwrap();
+        let table_lamports = Rent::default().minimum_balance(lookup_table_data.len());
+        let client = mock_client(Some((crate::id(), lookup_table_data)));
+
+        assert_eq!(
+            client
+                .fetch_closable_refund(&Pubkey::new_unique())
+                .await
+                .unwrap(),
+            table_lamports,
+        );
+    }
+}
@@ -0,0 +1,63 @@
+//! Compute unit cost estimates for program instructions.
+
+/// Approximate compute units consumed by an extend, regardless of the number
+/// of addresses.
+const EXTEND_BASE_COST: u64 = 5_933;
+/// Approximate compute units consumed per address appended by an extend,
+/// covering instruction deserialization, the realloc, and the copy.
+const EXTEND_COST_PER_NEW_ADDRESS: u64 = 293;
+/// Extends cost approximately one additional compute unit per this many
+/// addresses already stored in the table.
+const EXISTING_ADDRESSES_PER_COMPUTE_UNIT: u64 = 8;
+
+/// Returns a rough estimate of the compute units consumed by extending a
+/// table holding `num_existing` addresses with `num_new` addresses, when the
+/// payer must fund the reallocation.
+///
+/// Calibrated against `benches/compute_units.md`. The estimate is not exact,
+/// but it increases with both the number of existing and new addresses.
+pub fn estimate_extend_cost(num_existing: usize, num_new: usize) -> u64 {
+    EXTEND_BASE_COST
+        .saturating_add(EXTEND_COST_PER_NEW_ADDRESS.saturating_mul(num_new as u64))
+        .saturating_add((num_existing as u64).saturating_div(EXISTING_ADDRESSES_PER_COMPUTE_UNIT))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_extend_cost() {
+        // Within 1% of the benchmarked costs.
+        for (num_existing, num_new, benchmarked_cost) in [
+            (0, 1, 6_226),
+            (0, 10, 8_845),
+            (0, 38, 17_081),
+            (25, 38, 17_084),
+            (200, 38, 17_106),
+            (255, 1, 6_258),
+        ] {
+            let estimate = estimate_extend_cost(num_existing, num_new);
+            assert!(
+                estimate.abs_diff(benchmarked_cost) <= benchmarked_cost / 100,
+                "estimate {estimate} for {num_existing} + {num_new} is too far from \
+                 {benchmarked_cost}",
+            );
+        }
+
+        // Larger extends cost more.
+        for num_existing in [0, 1, 100, 255] {
+            for num_new in 1..38 {
+                assert!(
+                    estimate_extend_cost(num_existing, num_new + 1)
+                        > estimate_extend_cost(num_existing, num_new)
+                );
+            }
+        }
+        for num_existing in (0..=248).step_by(8) {
+            assert!(
+                estimate_extend_cost(num_existing + 8, 1) > estimate_extend_cost(num_existing, 1)
+            );
+        }
+    }
+}
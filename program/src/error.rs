@@ -32,8 +32,58 @@ pub enum AddressLookupTableError {
     /// Instruction changed the balance of a read-only account.
     #[error("Instruction changed the balance of a read-only account")]
     ReadonlyLamportsChanged,
+    /// Lookup table is full and cannot contain more addresses.
+    #[error("Lookup table is full and cannot contain more addresses")]
+    LookupTableFull = 20,
+    /// Extending the lookup table would exceed its maximum capacity.
+    #[error("Extending the lookup table would exceed its maximum capacity")]
+    ExtendExceedsCapacity,
+    /// None of the provided addresses are new to the lookup table.
+    #[error("None of the provided addresses are new to the lookup table")]
+    NoNewAddresses,
+    /// Closing the lookup table would overflow the recipient's lamports.
+    #[error("Closing the lookup table would overflow the recipient's lamports")]
+    CloseLamportsOverflow,
+    /// Lookup table account has already been closed.
+    #[error("Lookup table account has already been closed")]
+    LookupTableAlreadyClosed,
+    /// Too many new addresses were provided in a single extend instruction.
+    #[error("Too many new addresses were provided in a single extend instruction")]
+    TooManyNewAddresses,
+    /// Instruction discriminator does not match any known instruction.
+    #[error("Instruction discriminator does not match any known instruction")]
+    UnknownInstruction,
+    /// Lookup table needs more lamports to be extended, but no payer was
+    /// provided.
+    #[error("Lookup table needs more lamports to be extended, but no payer was provided")]
+    PayerRequiredForExtend,
 }
 
+/// Errors returned by off-chain prechecks such as `state::can_freeze`.
+///
+/// The program itself reports these conditions with the equivalent
+/// `ProgramError` variants, so they have no on-chain error code.
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+pub enum LookupTableValidationError {
+    /// Lookup table is frozen.
+    #[error("Lookup table is frozen")]
+    LookupTableFrozen,
+    /// Incorrect lookup table authority.
+    #[error("Incorrect lookup table authority")]
+    IncorrectAuthority,
+    /// Lookup table is deactivated.
+    #[error("Lookup table is deactivated")]
+    LookupTableDeactivated,
+    /// Lookup table is empty.
+    #[error("Lookup table is empty")]
+    LookupTableEmpty,
+}
+
+/// Error returned when parsing a `LookupTableStatus` from a string.
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+#[error("Invalid lookup table status: {0}")]
+pub struct ParseLookupTableStatusError(pub String);
+
 impl PrintProgramError for AddressLookupTableError {
     fn print<E>(&self) {
         msg!(&self.to_string());
@@ -1,11 +1,13 @@
 //! Program instruction types
 
 use {
+    crate::error::AddressLookupTableError,
     serde::{Deserialize, Serialize},
     solana_program::{
         clock::Slot,
         instruction::{AccountMeta, Instruction},
-        pubkey::Pubkey,
+        program_error::ProgramError,
+        pubkey::{Pubkey, PubkeyError},
         system_program,
     },
 };
@@ -70,6 +72,164 @@ pub enum AddressLookupTableInstruction {
     ///   1. `[SIGNER]` Current authority
     ///   2. `[WRITE]` Recipient of closed account lamports
     CloseLookupTable,
+
+    /// Transfer the authority of an address lookup table to a new address.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to update
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[]` New authority
+    TransferAuthority,
+
+    /// Reactivate an address lookup table which is still within its
+    /// deactivation cool-down period, cancelling the pending deactivation.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to reactivate
+    ///   1. `[SIGNER]` Current authority
+    ReactivateLookupTable,
+
+    /// Trim an address lookup table by removing trailing addresses, and
+    /// reclaim the lamports no longer required for rent exemption.
+    ///
+    /// Only addresses appended in the current slot, which are not yet
+    /// active, can be trimmed, so the index of an active address is never
+    /// freed for reuse.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to trim
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[WRITE]` Recipient of reclaimed lamports
+    TrimLookupTable { new_len: u8 },
+
+    /// Extend an address lookup table with new addresses, skipping any
+    /// which are already stored in the table or repeated in the input.
+    /// Fails if no addresses remain after filtering.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to extend
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[SIGNER, WRITE, OPTIONAL]` Account that will fund the table
+    ///      reallocation
+    ///   3. `[OPTIONAL]` System program for CPI.
+    ExtendLookupTableDedup { new_addresses: Vec<Pubkey> },
+
+    /// Close an address lookup table account, as `CloseLookupTable` does,
+    /// and also reassign the emptied account to the System program so it
+    /// matches the conventional shape of a closed account.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to close
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[WRITE]` Recipient of closed account lamports
+    CloseLookupTableAndReassign,
+}
+
+impl AddressLookupTableInstruction {
+    /// Serializes the instruction into raw instruction data.
+    pub fn pack(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("instruction serialization cannot fail")
+    }
+
+    /// Deserializes an instruction from raw instruction data, applying the
+    /// same input length limits as the program. See [`parse_instruction`].
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        parse_instruction(input)
+    }
+}
+
+// Maximum input buffer length that can be deserialized.
+// See `solana_sdk::packet::PACKET_DATA_SIZE`.
+const MAX_INPUT_LEN: usize = 1232;
+// Maximum vector length for new keys to be appended to a lookup table,
+// provided to the `ExtendLookupTable` and `ExtendLookupTableDedup`
+// instructions.
+// See comments below for `parse_instruction`.
+//
+// Take the maximum input length and subtract 4 bytes for the discriminator,
+// 8 bytes for the vector length, then divide that by the size of a `Pubkey`.
+const MAX_NEW_KEYS_VECTOR_LEN: usize = (MAX_INPUT_LEN - 4 - 8) / 32;
+
+/// The maximum number of addresses which can be appended to a lookup table
+/// by a single `ExtendLookupTable` or `ExtendLookupTableDedup` instruction.
+///
+/// Derived from the maximum instruction input length of 1232 bytes, less 4
+/// bytes for the instruction discriminator and 8 bytes for the vector
+/// length, divided by the size of a `Pubkey`.
+pub const MAX_EXTEND_ADDRESSES_PER_IX: usize = 38;
+
+const _: () = assert!(MAX_EXTEND_ADDRESSES_PER_IX == MAX_NEW_KEYS_VECTOR_LEN);
+
+// Number of instructions supported by the program. Any discriminator at or
+// past this value is reported as `AddressLookupTableError::UnknownInstruction`.
+const NUM_INSTRUCTIONS: u32 = 10;
+
+// Stub of `AddressLookupTableInstruction` for partial deserialization.
+// Keep in sync with the program's instructions in `instructions`.
+#[allow(clippy::enum_variant_names)]
+#[cfg_attr(test, derive(strum_macros::EnumIter))]
+#[derive(serde::Serialize, serde::Deserialize, PartialEq)]
+enum InstructionStub {
+    CreateLookupTable,
+    FreezeLookupTable,
+    ExtendLookupTable { vector_len: u64 },
+    DeactivateLookupTable,
+    CloseLookupTable,
+    TransferAuthority,
+    ReactivateLookupTable,
+    TrimLookupTable,
+    ExtendLookupTableDedup { vector_len: u64 },
+    CloseLookupTableAndReassign,
+}
+
+// [Core BPF]: The original Address Lookup Table builtin leverages the
+// `solana_sdk::program_utils::limited_deserialize` method to cap the length of
+// the input buffer at `MAX_INPUT_LEN` (1232). As a result, any input buffer
+// larger than `MAX_INPUT_LEN` will abort deserialization and return
+// `InstructionError::InvalidInstructionData`.
+//
+// Howevever, since `ExtendLookupTable` contains a vector of `Pubkey`, the
+// `limited_deserialize` method will still read the vector's length and attempt
+// to allocate a vector of the designated size. For extremely large length
+// values, this can cause the initial allocation of a large vector to exhuast
+// the BPF program's heap before deserialization can proceed.
+//
+// To mitigate this memory issue, the BPF version of the program has been
+// designed to "peek" the length value for `ExtendLookupTable`, and ensure it
+// cannot allocate a vector that would otherwise violate the input buffer
+// length restriction. Unlike the builtin, such vectors are rejected with
+// `AddressLookupTableError::TooManyNewAddresses` rather than
+// `InvalidInstructionData`, to distinguish oversized batches from malformed
+// input.
+/// Deserializes an `AddressLookupTableInstruction` from raw instruction data,
+/// applying the same input length limits as the program.
+///
+/// Extend instructions with more than [`MAX_EXTEND_ADDRESSES_PER_IX`]
+/// addresses are rejected with
+/// [`AddressLookupTableError::TooManyNewAddresses`].
+/// Unsupported instruction discriminators are rejected with
+/// [`AddressLookupTableError::UnknownInstruction`].
+pub fn parse_instruction(input: &[u8]) -> Result<AddressLookupTableInstruction, ProgramError> {
+    match bincode::deserialize::<InstructionStub>(input).map_err(|_| {
+        // Distinguish a well-formed but unsupported discriminator from
+        // malformed instruction data.
+        match bincode::deserialize::<u32>(input) {
+            Ok(discriminator) if discriminator >= NUM_INSTRUCTIONS => {
+                AddressLookupTableError::UnknownInstruction.into()
+            }
+            _ => ProgramError::InvalidInstructionData,
+        }
+    })? {
+        InstructionStub::ExtendLookupTable { vector_len }
+        | InstructionStub::ExtendLookupTableDedup { vector_len }
+            if vector_len as usize > MAX_EXTEND_ADDRESSES_PER_IX =>
+        {
+            return Err(AddressLookupTableError::TooManyNewAddresses.into());
+        }
+        _ => {}
+    }
+    solana_program::program_utils::limited_deserialize(input, MAX_INPUT_LEN as u64)
+        .map_err(|_| ProgramError::InvalidInstructionData)
 }
 
 /// Derives the address of an address table account from a wallet address and a
@@ -84,6 +244,118 @@ pub fn derive_lookup_table_address(
     )
 }
 
+/// Derives the address of an address table account for each of the
+/// candidate recent block slots, returning each slot with its derived
+/// address and bump seed.
+///
+/// Useful for recovering a table's address when its authority is known but
+/// its exact creation slot is not: the returned addresses can be checked
+/// against on-chain accounts.
+pub fn derive_lookup_table_addresses_for_slots(
+    authority_address: &Pubkey,
+    recent_block_slots: impl IntoIterator<Item = Slot>,
+) -> Vec<(Slot, Pubkey, u8)> {
+    recent_block_slots
+        .into_iter()
+        .map(|recent_block_slot| {
+            let (lookup_table_address, bump_seed) =
+                derive_lookup_table_address(authority_address, recent_block_slot);
+            (recent_block_slot, lookup_table_address, bump_seed)
+        })
+        .collect()
+}
+
+/// Creates the address of an address table account from a wallet address,
+/// a recent block's slot, and a known bump seed.
+///
+/// Unlike [`derive_lookup_table_address`], this does not search for the
+/// canonical bump seed, so it is cheap enough to use on-chain when the bump
+/// has been cached. Callers are responsible for ensuring the bump is the
+/// canonical one returned by [`derive_lookup_table_address`].
+pub fn create_lookup_table_address(
+    authority_address: &Pubkey,
+    recent_block_slot: Slot,
+    bump: u8,
+) -> Result<Pubkey, PubkeyError> {
+    Pubkey::create_program_address(
+        &[
+            authority_address.as_ref(),
+            &recent_block_slot.to_le_bytes(),
+            &[bump],
+        ],
+        &crate::id(),
+    )
+}
+
+/// Derives every valid address of an address table account for a wallet
+/// address and a recent block's slot, one per bump seed that yields an
+/// off-curve address.
+///
+/// Entries are ordered from the highest bump seed down, so the first entry
+/// is the canonical address returned by [`derive_lookup_table_address`].
+/// The program accepts any of these addresses when created with the
+/// matching bump seed.
+pub fn find_lookup_table_address_with_all_bumps(
+    authority_address: &Pubkey,
+    recent_block_slot: Slot,
+) -> Vec<(Pubkey, u8)> {
+    (0..=u8::MAX)
+        .rev()
+        .filter_map(|bump| {
+            create_lookup_table_address(authority_address, recent_block_slot, bump)
+                .ok()
+                .map(|lookup_table_address| (lookup_table_address, bump))
+        })
+        .collect()
+}
+
+/// Returns the bump seed with which `lookup_table_address` is derived from
+/// a wallet address and a recent block's slot, or `None` if the address
+/// can't be derived from them with any bump seed.
+///
+/// Useful for diagnosing a create instruction rejected with "Table address
+/// must match derived address": a `None` result points to a mismatched
+/// authority or slot, while a non-canonical bump points to a mismatched
+/// bump seed.
+pub fn find_lookup_table_address_bump(
+    lookup_table_address: &Pubkey,
+    authority_address: &Pubkey,
+    recent_block_slot: Slot,
+) -> Option<u8> {
+    (0..=u8::MAX).rev().find(|bump| {
+        create_lookup_table_address(authority_address, recent_block_slot, *bump).as_ref()
+            == Ok(lookup_table_address)
+    })
+}
+
+/// Returns `true` if `slot` is one of the provided `SlotHashes` slots, and
+/// so can be used as the recent slot when creating a lookup table.
+///
+/// The create instruction fails for slots that are missing from the
+/// `SlotHashes` sysvar, so clients can fetch the sysvar once and check their
+/// chosen slot locally before submitting a transaction.
+pub fn is_recent_slot(slot: Slot, slot_hash_slots: &[Slot]) -> bool {
+    slot_hash_slots.contains(&slot)
+}
+
+// Number of slots to step back from the current slot when choosing a recent
+// slot for a new lookup table.
+const RECENT_SLOT_MARGIN: Slot = 8;
+
+/// Returns a slot, a few slots behind `current_slot`, to pass as the recent
+/// slot when creating a lookup table.
+///
+/// The create instruction requires a slot present in the `SlotHashes`
+/// sysvar of the bank that processes it. The current slot as seen by a
+/// client may not have been added to `SlotHashes` yet, or the transaction
+/// may land on a fork that has not seen it, so stepping back a few slots
+/// leaves margin for propagation. Skipped slots are never present in
+/// `SlotHashes`, so callers holding the sysvar should still confirm the
+/// result with [`is_recent_slot`].
+pub fn recommended_recent_slot(current_slot: Slot) -> Slot {
+    current_slot.saturating_sub(RECENT_SLOT_MARGIN)
+}
+
 // [Core BPF]: `create_lookup_table_signed` has been removed, since feature
 // "FKAcEvNgSY79RpqsPNUV5gDyumopH4cEHqUxyfm8b8Ap"
 // (relax_authority_signer_check_for_lookup_table_creation) has been activated
@@ -96,33 +368,133 @@ pub fn create_lookup_table(
     payer_address: Pubkey,
     recent_slot: Slot,
 ) -> (Instruction, Pubkey) {
+    let (instruction, lookup_table_address, _) =
+        derive_and_create_lookup_table(authority_address, payer_address, recent_slot);
+    (instruction, lookup_table_address)
+}
+
+/// Named arguments for [`create_lookup_table_with_params`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CreateLookupTableParams {
+    /// The authority which must sign to modify the new table.
+    pub authority: Pubkey,
+    /// The account which funds the new table's rent.
+    pub payer: Pubkey,
+    /// A recent slot, present in the `SlotHashes` sysvar, from which the
+    /// table's address is derived.
+    pub recent_slot: Slot,
+}
+
+/// Constructs an instruction to create a table account from named
+/// arguments, and returns the instruction and the table account's derived
+/// address.
+///
+/// Equivalent to [`create_lookup_table`], but avoids mixing up the
+/// authority and payer at call sites.
+pub fn create_lookup_table_with_params(params: CreateLookupTableParams) -> (Instruction, Pubkey) {
+    create_lookup_table(params.authority, params.payer, params.recent_slot)
+}
+
+/// Constructs an instruction to create a table account using a known bump
+/// seed, and returns the instruction and the table account's address.
+///
+/// This skips the canonical bump search performed by
+/// [`create_lookup_table`]. Returns an error if the bump seed does not
+/// produce a valid program address. The program accepts any valid bump
+/// seed, but only the canonical one yields the address returned by
+/// [`derive_lookup_table_address`].
+pub fn create_lookup_table_with_bump(
+    authority_address: Pubkey,
+    payer_address: Pubkey,
+    recent_slot: Slot,
+    bump_seed: u8,
+) -> Result<(Instruction, Pubkey), PubkeyError> {
+    let lookup_table_address =
+        create_lookup_table_address(&authority_address, recent_slot, bump_seed)?;
+    let instruction = create_lookup_table_instruction(
+        lookup_table_address,
+        authority_address,
+        payer_address,
+        recent_slot,
+        bump_seed,
+    );
+    Ok((instruction, lookup_table_address))
+}
+
+fn derive_and_create_lookup_table(
+    authority_address: Pubkey,
+    payer_address: Pubkey,
+    recent_slot: Slot,
+) -> (Instruction, Pubkey, u8) {
     let (lookup_table_address, bump_seed) =
         derive_lookup_table_address(&authority_address, recent_slot);
+    let instruction = create_lookup_table_instruction(
+        lookup_table_address,
+        authority_address,
+        payer_address,
+        recent_slot,
+        bump_seed,
+    );
+    (instruction, lookup_table_address, bump_seed)
+}
 
-    let instruction = Instruction::new_with_bincode(
+fn create_lookup_table_instruction(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Pubkey,
+    recent_slot: Slot,
+    bump_seed: u8,
+) -> Instruction {
+    Instruction::new_with_bytes(
         crate::id(),
         &AddressLookupTableInstruction::CreateLookupTable {
             recent_slot,
             bump_seed,
-        },
+        }
+        .pack(),
         vec![
             AccountMeta::new(lookup_table_address, false),
             AccountMeta::new_readonly(authority_address, false),
             AccountMeta::new(payer_address, true),
             AccountMeta::new_readonly(system_program::id(), false),
         ],
-    );
+    )
+}
 
-    (instruction, lookup_table_address)
+/// Constructs an instruction to create a table account if it does not
+/// already exist, and returns the instruction and the table account's
+/// derived address.
+///
+/// The instruction is safe to replay: when the table account is already
+/// owned by the Address Lookup Table program, the processor returns
+/// successfully without modifying the account or charging the payer.
+/// Note that a replay does not verify that the existing table was
+/// created by the same authority and recent slot.
+pub fn create_lookup_table_idempotent(
+    authority_address: Pubkey,
+    payer_address: Pubkey,
+    recent_slot: Slot,
+) -> (Instruction, Pubkey) {
+    create_lookup_table(authority_address, payer_address, recent_slot)
+}
+
+/// Like [`create_lookup_table_idempotent`], but also returns the bump
+/// seed of the table account's derived address.
+pub fn create_lookup_table_idempotent_with_bump(
+    authority_address: Pubkey,
+    payer_address: Pubkey,
+    recent_slot: Slot,
+) -> (Instruction, Pubkey, u8) {
+    derive_and_create_lookup_table(authority_address, payer_address, recent_slot)
 }
 
 /// Constructs an instruction that freezes an address lookup
 /// table so that it can never be closed or extended again. Empty
 /// lookup tables cannot be frozen.
 pub fn freeze_lookup_table(lookup_table_address: Pubkey, authority_address: Pubkey) -> Instruction {
-    Instruction::new_with_bincode(
+    Instruction::new_with_bytes(
         crate::id(),
-        &AddressLookupTableInstruction::FreezeLookupTable,
+        &AddressLookupTableInstruction::FreezeLookupTable.pack(),
         vec![
             AccountMeta::new(lookup_table_address, false),
             AccountMeta::new_readonly(authority_address, true),
@@ -138,6 +510,51 @@ pub fn extend_lookup_table(
     payer_address: Option<Pubkey>,
     new_addresses: Vec<Pubkey>,
 ) -> Instruction {
+    Instruction::new_with_bytes(
+        crate::id(),
+        &AddressLookupTableInstruction::ExtendLookupTable { new_addresses }.pack(),
+        extend_lookup_table_accounts(lookup_table_address, authority_address, payer_address),
+    )
+}
+
+/// Constructs an instruction which extends an address lookup
+/// table account with new addresses, without providing a payer.
+///
+/// The table account must already hold enough lamports to be rent exempt
+/// once the new addresses are appended. If it is underfunded, the
+/// instruction fails with
+/// [`AddressLookupTableError::PayerRequiredForExtend`], since there is no
+/// payer to cover the shortfall.
+pub fn extend_lookup_table_prepaid(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    new_addresses: Vec<Pubkey>,
+) -> Instruction {
+    extend_lookup_table(lookup_table_address, authority_address, None, new_addresses)
+}
+
+/// Constructs an instruction which extends an address lookup
+/// table account with new addresses, skipping any which are already
+/// stored in the table. The instruction fails if none of the addresses
+/// are new.
+pub fn extend_lookup_table_dedup(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Option<Pubkey>,
+    new_addresses: Vec<Pubkey>,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        crate::id(),
+        &AddressLookupTableInstruction::ExtendLookupTableDedup { new_addresses }.pack(),
+        extend_lookup_table_accounts(lookup_table_address, authority_address, payer_address),
+    )
+}
+
+fn extend_lookup_table_accounts(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Option<Pubkey>,
+) -> Vec<AccountMeta> {
     let mut accounts = vec![
         AccountMeta::new(lookup_table_address, false),
         AccountMeta::new_readonly(authority_address, true),
@@ -150,11 +567,95 @@ pub fn extend_lookup_table(
         ]);
     }
 
-    Instruction::new_with_bincode(
-        crate::id(),
-        &AddressLookupTableInstruction::ExtendLookupTable { new_addresses },
-        accounts,
-    )
+    accounts
+}
+
+/// Constructs a list of instructions which extend an address lookup
+/// table account with new addresses, splitting the addresses into
+/// chunks small enough to fit in a single `ExtendLookupTable`
+/// instruction.
+///
+/// Every instruction carries the same payer accounts, since each
+/// extension may require additional lamports. The instructions must be
+/// processed in order, and should be sent in separate transactions if
+/// combining them would exceed the maximum packet size.
+pub fn extend_lookup_table_chunked(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Option<Pubkey>,
+    new_addresses: Vec<Pubkey>,
+) -> Vec<Instruction> {
+    new_addresses
+        .chunks(MAX_EXTEND_ADDRESSES_PER_IX)
+        .map(|chunk| {
+            extend_lookup_table(
+                lookup_table_address,
+                authority_address,
+                payer_address,
+                chunk.to_vec(),
+            )
+        })
+        .collect()
+}
+
+/// Constructs the same instructions as [`extend_lookup_table_chunked`], but
+/// consumes the new addresses lazily from an iterator.
+///
+/// Only one chunk of addresses is buffered at a time, so callers syncing a
+/// large generated address list don't need to collect it first.
+pub fn extend_instructions_from_iter(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    payer_address: Option<Pubkey>,
+    new_addresses: impl IntoIterator<Item = Pubkey>,
+) -> Vec<Instruction> {
+    let mut new_addresses = new_addresses.into_iter();
+    let mut instructions = Vec::new();
+    loop {
+        let chunk: Vec<Pubkey> = new_addresses
+            .by_ref()
+            .take(MAX_EXTEND_ADDRESSES_PER_IX)
+            .collect();
+        if chunk.is_empty() {
+            break;
+        }
+        instructions.push(extend_lookup_table(
+            lookup_table_address,
+            authority_address,
+            payer_address,
+            chunk,
+        ));
+    }
+    instructions
+}
+
+/// Constructs the instructions to create an address lookup table and extend
+/// it with an initial batch of addresses, returning the instructions and the
+/// table account's derived address.
+///
+/// The create instruction is followed by the extend instructions from
+/// [`extend_lookup_table_chunked`], which the authority must also sign. All
+/// of the instructions only fit in a single transaction for small address
+/// counts; otherwise, the create instruction and each extend instruction
+/// should be sent in order in separate transactions.
+pub fn create_and_extend_lookup_table(
+    authority_address: Pubkey,
+    payer_address: Pubkey,
+    recent_slot: Slot,
+    initial_addresses: Vec<Pubkey>,
+) -> (Vec<Instruction>, Pubkey) {
+    let (create_instruction, lookup_table_address) =
+        create_lookup_table(authority_address, payer_address, recent_slot);
+
+    let mut instructions = vec![create_instruction];
+    instructions.extend(extend_lookup_table_chunked(
+        lookup_table_address,
+        authority_address,
+        Some(payer_address),
+        initial_addresses,
+    ));
+
+    (instructions, lookup_table_address)
 }
 
 /// Constructs an instruction that deactivates an address lookup
@@ -164,9 +665,9 @@ pub fn deactivate_lookup_table(
     lookup_table_address: Pubkey,
     authority_address: Pubkey,
 ) -> Instruction {
-    Instruction::new_with_bincode(
+    Instruction::new_with_bytes(
         crate::id(),
-        &AddressLookupTableInstruction::DeactivateLookupTable,
+        &AddressLookupTableInstruction::DeactivateLookupTable.pack(),
         vec![
             AccountMeta::new(lookup_table_address, false),
             AccountMeta::new_readonly(authority_address, true),
@@ -182,9 +683,9 @@ pub fn close_lookup_table(
     authority_address: Pubkey,
     recipient_address: Pubkey,
 ) -> Instruction {
-    Instruction::new_with_bincode(
+    Instruction::new_with_bytes(
         crate::id(),
-        &AddressLookupTableInstruction::CloseLookupTable,
+        &AddressLookupTableInstruction::CloseLookupTable.pack(),
         vec![
             AccountMeta::new(lookup_table_address, false),
             AccountMeta::new_readonly(authority_address, true),
@@ -192,3 +693,949 @@ pub fn close_lookup_table(
         ],
     )
 }
+
+/// Constructs an instruction that closes an address lookup table
+/// account and reassigns it to the System program. The account's lamports
+/// are drained into the specified recipient address.
+///
+/// Unlike [`close_lookup_table`], the closed account is no longer owned by
+/// the program once the instruction succeeds.
+pub fn close_lookup_table_and_reassign(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    recipient_address: Pubkey,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        crate::id(),
+        &AddressLookupTableInstruction::CloseLookupTableAndReassign.pack(),
+        vec![
+            AccountMeta::new(lookup_table_address, false),
+            AccountMeta::new_readonly(authority_address, true),
+            AccountMeta::new(recipient_address, false),
+        ],
+    )
+}
+
+/// Returns one instruction per lookup table which closes the table
+/// account, draining all of their lamports to the same recipient address.
+///
+/// Each table must already be fully deactivated, meaning its deactivation
+/// slot is no longer present in the `SlotHashes` sysvar, or the
+/// corresponding instruction will fail.
+pub fn close_lookup_tables(
+    lookup_table_addresses: &[Pubkey],
+    authority_address: Pubkey,
+    recipient_address: Pubkey,
+) -> Vec<Instruction> {
+    lookup_table_addresses
+        .iter()
+        .map(|lookup_table_address| {
+            close_lookup_table(*lookup_table_address, authority_address, recipient_address)
+        })
+        .collect()
+}
+
+/// Returns the pair of instructions needed to retire an address lookup
+/// table: one which deactivates the table, and one which closes it.
+///
+/// The two instructions must be submitted in separate transactions. The
+/// close instruction will fail until the table has finished its cooldown,
+/// meaning its deactivation slot is no longer present in the `SlotHashes`
+/// sysvar (roughly 512 slots), so it should only be submitted once the
+/// cooldown has elapsed.
+pub fn deactivate_lookup_table_instructions(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    recipient_address: Pubkey,
+) -> (Instruction, Instruction) {
+    (
+        deactivate_lookup_table(lookup_table_address, authority_address),
+        close_lookup_table(lookup_table_address, authority_address, recipient_address),
+    )
+}
+
+/// Returns the instructions needed to replace an address lookup table with
+/// a new one under the same authority, along with the new table's derived
+/// address.
+///
+/// The instructions are, in order: create the new table, deactivate the
+/// old table, and close the old table. The create and deactivate
+/// instructions may share a transaction, so the old table is only
+/// deactivated if the new one is created. The close instruction must be
+/// submitted in a later transaction, once the old table has finished its
+/// cooldown (see [`deactivate_lookup_table_instructions`]); until then it
+/// fails. The new table still needs to be extended before it replaces the
+/// old one in transactions.
+pub fn rotate_lookup_table_instructions(
+    old_lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    recent_slot: Slot,
+    payer_address: Pubkey,
+    recipient_address: Pubkey,
+) -> (Vec<Instruction>, Pubkey) {
+    let (create_instruction, new_lookup_table_address) =
+        create_lookup_table(authority_address, payer_address, recent_slot);
+    let (deactivate_instruction, close_instruction) = deactivate_lookup_table_instructions(
+        old_lookup_table_address,
+        authority_address,
+        recipient_address,
+    );
+
+    (
+        vec![
+            create_instruction,
+            deactivate_instruction,
+            close_instruction,
+        ],
+        new_lookup_table_address,
+    )
+}
+
+/// Constructs an instruction that transfers the authority of an
+/// address lookup table to a new address. Frozen and deactivated
+/// lookup tables cannot have their authority transferred.
+pub fn transfer_authority(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    new_authority_address: Pubkey,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        crate::id(),
+        &AddressLookupTableInstruction::TransferAuthority.pack(),
+        vec![
+            AccountMeta::new(lookup_table_address, false),
+            AccountMeta::new_readonly(authority_address, true),
+            AccountMeta::new_readonly(new_authority_address, false),
+        ],
+    )
+}
+
+/// Constructs an instruction that reactivates an address lookup
+/// table which was deactivated but has not yet finished its
+/// cool-down period. Fully deactivated lookup tables cannot be
+/// reactivated.
+pub fn reactivate_lookup_table(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        crate::id(),
+        &AddressLookupTableInstruction::ReactivateLookupTable.pack(),
+        vec![
+            AccountMeta::new(lookup_table_address, false),
+            AccountMeta::new_readonly(authority_address, true),
+        ],
+    )
+}
+
+/// Constructs an instruction that trims an address lookup table
+/// down to `new_len` addresses. The lamports no longer required for
+/// rent exemption are transferred to the recipient address.
+///
+/// Only addresses appended in the current slot can be trimmed, so the
+/// instruction must land in the same slot as the extension it undoes.
+pub fn trim_lookup_table(
+    lookup_table_address: Pubkey,
+    authority_address: Pubkey,
+    recipient_address: Pubkey,
+    new_len: u8,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        crate::id(),
+        &AddressLookupTableInstruction::TrimLookupTable { new_len }.pack(),
+        vec![
+            AccountMeta::new(lookup_table_address, false),
+            AccountMeta::new_readonly(authority_address, true),
+            AccountMeta::new(recipient_address, false),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_instruction_serialization(
+        stub: &InstructionStub,
+        instruction: &AddressLookupTableInstruction,
+        len: usize,
+    ) {
+        assert_eq!(
+            bincode::serialize(&stub).unwrap(),
+            bincode::serialize(&instruction).unwrap()[0..len],
+        )
+    }
+
+    #[test]
+    fn test_instruction_stubs() {
+        assert_eq!(
+            <InstructionStub as strum::IntoEnumIterator>::iter().count(),
+            <AddressLookupTableInstruction as strum::IntoEnumIterator>::iter().count(),
+        );
+        assert_eq!(
+            <InstructionStub as strum::IntoEnumIterator>::iter().count(),
+            NUM_INSTRUCTIONS as usize,
+        );
+
+        assert_instruction_serialization(
+            &InstructionStub::CreateLookupTable,
+            &AddressLookupTableInstruction::CreateLookupTable {
+                recent_slot: 0,
+                bump_seed: 0,
+            },
+            4,
+        );
+        assert_instruction_serialization(
+            &InstructionStub::FreezeLookupTable,
+            &AddressLookupTableInstruction::FreezeLookupTable,
+            4,
+        );
+        assert_instruction_serialization(
+            &InstructionStub::ExtendLookupTable { vector_len: 4 },
+            &AddressLookupTableInstruction::ExtendLookupTable {
+                new_addresses: vec![Pubkey::new_unique(); 4],
+            },
+            12, // Check the vector length as well.
+        );
+        assert_instruction_serialization(
+            &InstructionStub::DeactivateLookupTable,
+            &AddressLookupTableInstruction::DeactivateLookupTable,
+            4,
+        );
+        assert_instruction_serialization(
+            &InstructionStub::CloseLookupTable,
+            &AddressLookupTableInstruction::CloseLookupTable,
+            4,
+        );
+        assert_instruction_serialization(
+            &InstructionStub::TransferAuthority,
+            &AddressLookupTableInstruction::TransferAuthority,
+            4,
+        );
+        assert_instruction_serialization(
+            &InstructionStub::ReactivateLookupTable,
+            &AddressLookupTableInstruction::ReactivateLookupTable,
+            4,
+        );
+        assert_instruction_serialization(
+            &InstructionStub::TrimLookupTable,
+            &AddressLookupTableInstruction::TrimLookupTable { new_len: 0 },
+            4,
+        );
+        assert_instruction_serialization(
+            &InstructionStub::ExtendLookupTableDedup { vector_len: 4 },
+            &AddressLookupTableInstruction::ExtendLookupTableDedup {
+                new_addresses: vec![Pubkey::new_unique(); 4],
+            },
+            12, // Check the vector length as well.
+        );
+        assert_instruction_serialization(
+            &InstructionStub::CloseLookupTableAndReassign,
+            &AddressLookupTableInstruction::CloseLookupTableAndReassign,
+            4,
+        );
+    }
+
+    #[test]
+    fn test_pack_unpack() {
+        let mut new_addresses = Vec::with_capacity(MAX_EXTEND_ADDRESSES_PER_IX);
+        new_addresses.resize_with(MAX_EXTEND_ADDRESSES_PER_IX, Pubkey::new_unique);
+
+        for instruction in <AddressLookupTableInstruction as strum::IntoEnumIterator>::iter() {
+            // Populate the variants carrying data with non-default values.
+            let instruction = match instruction {
+                AddressLookupTableInstruction::CreateLookupTable { .. } => {
+                    AddressLookupTableInstruction::CreateLookupTable {
+                        recent_slot: 123,
+                        bump_seed: 254,
+                    }
+                }
+                AddressLookupTableInstruction::ExtendLookupTable { .. } => {
+                    AddressLookupTableInstruction::ExtendLookupTable {
+                        new_addresses: new_addresses.clone(),
+                    }
+                }
+                AddressLookupTableInstruction::TrimLookupTable { .. } => {
+                    AddressLookupTableInstruction::TrimLookupTable { new_len: 7 }
+                }
+                AddressLookupTableInstruction::ExtendLookupTableDedup { .. } => {
+                    AddressLookupTableInstruction::ExtendLookupTableDedup {
+                        new_addresses: new_addresses.clone(),
+                    }
+                }
+                instruction => instruction,
+            };
+
+            let data = instruction.pack();
+            assert_eq!(data, bincode::serialize(&instruction).unwrap());
+            assert_eq!(
+                AddressLookupTableInstruction::unpack(&data),
+                Ok(instruction)
+            );
+        }
+
+        // Oversized `ExtendLookupTable` and `ExtendLookupTableDedup` vectors.
+        new_addresses.push(Pubkey::new_unique());
+        for instruction in [
+            AddressLookupTableInstruction::ExtendLookupTable {
+                new_addresses: new_addresses.clone(),
+            },
+            AddressLookupTableInstruction::ExtendLookupTableDedup { new_addresses },
+        ] {
+            assert_eq!(
+                AddressLookupTableInstruction::unpack(&instruction.pack()),
+                Err(AddressLookupTableError::TooManyNewAddresses.into()),
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_instruction() {
+        let lookup_table_address = Pubkey::new_unique();
+        let authority_address = Pubkey::new_unique();
+        let payer_address = Pubkey::new_unique();
+        let recipient_address = Pubkey::new_unique();
+
+        let (create_instruction, _) = create_lookup_table(authority_address, payer_address, 123);
+        let (_, bump_seed) = derive_lookup_table_address(&authority_address, 123);
+        let mut new_addresses = Vec::with_capacity(MAX_EXTEND_ADDRESSES_PER_IX);
+        new_addresses.resize_with(MAX_EXTEND_ADDRESSES_PER_IX, Pubkey::new_unique);
+
+        for (instruction, expected) in [
+            (
+                create_instruction,
+                AddressLookupTableInstruction::CreateLookupTable {
+                    recent_slot: 123,
+                    bump_seed,
+                },
+            ),
+            (
+                freeze_lookup_table(lookup_table_address, authority_address),
+                AddressLookupTableInstruction::FreezeLookupTable,
+            ),
+            (
+                extend_lookup_table(
+                    lookup_table_address,
+                    authority_address,
+                    Some(payer_address),
+                    new_addresses.clone(),
+                ),
+                AddressLookupTableInstruction::ExtendLookupTable {
+                    new_addresses: new_addresses.clone(),
+                },
+            ),
+            (
+                deactivate_lookup_table(lookup_table_address, authority_address),
+                AddressLookupTableInstruction::DeactivateLookupTable,
+            ),
+            (
+                close_lookup_table(lookup_table_address, authority_address, recipient_address),
+                AddressLookupTableInstruction::CloseLookupTable,
+            ),
+            (
+                transfer_authority(lookup_table_address, authority_address, recipient_address),
+                AddressLookupTableInstruction::TransferAuthority,
+            ),
+            (
+                reactivate_lookup_table(lookup_table_address, authority_address),
+                AddressLookupTableInstruction::ReactivateLookupTable,
+            ),
+            (
+                trim_lookup_table(
+                    lookup_table_address,
+                    authority_address,
+                    recipient_address,
+                    7,
+                ),
+                AddressLookupTableInstruction::TrimLookupTable { new_len: 7 },
+            ),
+            (
+                extend_lookup_table_dedup(
+                    lookup_table_address,
+                    authority_address,
+                    Some(payer_address),
+                    new_addresses.clone(),
+                ),
+                AddressLookupTableInstruction::ExtendLookupTableDedup {
+                    new_addresses: new_addresses.clone(),
+                },
+            ),
+            (
+                close_lookup_table_and_reassign(
+                    lookup_table_address,
+                    authority_address,
+                    recipient_address,
+                ),
+                AddressLookupTableInstruction::CloseLookupTableAndReassign,
+            ),
+        ] {
+            assert_eq!(parse_instruction(&instruction.data), Ok(expected));
+        }
+
+        // Oversized `ExtendLookupTable` and `ExtendLookupTableDedup` vectors.
+        new_addresses.push(Pubkey::new_unique());
+        let instruction = extend_lookup_table(
+            lookup_table_address,
+            authority_address,
+            None,
+            new_addresses.clone(),
+        );
+        assert_eq!(
+            parse_instruction(&instruction.data),
+            Err(AddressLookupTableError::TooManyNewAddresses.into())
+        );
+        let instruction =
+            extend_lookup_table_dedup(lookup_table_address, authority_address, None, new_addresses);
+        assert_eq!(
+            parse_instruction(&instruction.data),
+            Err(AddressLookupTableError::TooManyNewAddresses.into())
+        );
+
+        // Vector length exceeding the input buffer limit, without the
+        // addresses themselves.
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(
+            parse_instruction(&data),
+            Err(AddressLookupTableError::TooManyNewAddresses.into())
+        );
+
+        // The limit applies to the bytes read rather than the input length,
+        // so, as with the builtin, trailing data past `MAX_INPUT_LEN` is
+        // ignored.
+        let mut data = deactivate_lookup_table(lookup_table_address, authority_address).data;
+        data.resize(MAX_INPUT_LEN.saturating_add(1), 0);
+        assert_eq!(
+            parse_instruction(&data),
+            Ok(AddressLookupTableInstruction::DeactivateLookupTable)
+        );
+
+        // Discriminators past the last instruction.
+        for discriminator in [NUM_INSTRUCTIONS, u32::MAX] {
+            assert_eq!(
+                parse_instruction(&discriminator.to_le_bytes()),
+                Err(AddressLookupTableError::UnknownInstruction.into())
+            );
+        }
+
+        // Truncated data.
+        let instruction = trim_lookup_table(
+            lookup_table_address,
+            authority_address,
+            recipient_address,
+            7,
+        );
+        for len in [1, 4] {
+            assert_eq!(
+                parse_instruction(&instruction.data[..len]),
+                Err(ProgramError::InvalidInstructionData)
+            );
+        }
+
+        // Unparseable data.
+        assert_eq!(
+            parse_instruction(&[]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_extend_lookup_table_prepaid() {
+        let lookup_table_address = Pubkey::new_unique();
+        let authority_address = Pubkey::new_unique();
+        let new_addresses = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let instruction = extend_lookup_table_prepaid(
+            lookup_table_address,
+            authority_address,
+            new_addresses.clone(),
+        );
+
+        assert_eq!(instruction.program_id, crate::id());
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                AccountMeta::new(lookup_table_address, false),
+                AccountMeta::new_readonly(authority_address, true),
+            ],
+        );
+        assert_eq!(
+            parse_instruction(&instruction.data),
+            Ok(AddressLookupTableInstruction::ExtendLookupTable { new_addresses }),
+        );
+    }
+
+    #[test]
+    fn test_max_extend_addresses_per_ix() {
+        assert_eq!(MAX_EXTEND_ADDRESSES_PER_IX, MAX_NEW_KEYS_VECTOR_LEN);
+    }
+
+    #[test]
+    fn test_extend_lookup_table_chunked() {
+        let lookup_table_address = Pubkey::new_unique();
+        let authority_address = Pubkey::new_unique();
+        let payer_address = Pubkey::new_unique();
+
+        for (num_new_addresses, expected_chunk_lens) in [
+            (0, vec![]),
+            (1, vec![1]),
+            (38, vec![38]),
+            (39, vec![38, 1]),
+            (256, vec![38, 38, 38, 38, 38, 38, 28]),
+        ] {
+            let mut new_addresses = Vec::with_capacity(num_new_addresses);
+            new_addresses.resize_with(num_new_addresses, Pubkey::new_unique);
+
+            let instructions = extend_lookup_table_chunked(
+                lookup_table_address,
+                authority_address,
+                Some(payer_address),
+                new_addresses.clone(),
+            );
+            assert_eq!(instructions.len(), expected_chunk_lens.len());
+
+            let mut chunked_addresses = Vec::with_capacity(num_new_addresses);
+            for (instruction, expected_chunk_len) in instructions.iter().zip(expected_chunk_lens) {
+                assert_eq!(
+                    instruction.accounts,
+                    vec![
+                        AccountMeta::new(lookup_table_address, false),
+                        AccountMeta::new_readonly(authority_address, true),
+                        AccountMeta::new(payer_address, true),
+                        AccountMeta::new_readonly(system_program::id(), false),
+                    ]
+                );
+                match bincode::deserialize(&instruction.data).unwrap() {
+                    AddressLookupTableInstruction::ExtendLookupTable { new_addresses } => {
+                        assert_eq!(new_addresses.len(), expected_chunk_len);
+                        chunked_addresses.extend(new_addresses);
+                    }
+                    _ => panic!("Expected an `ExtendLookupTable` instruction"),
+                }
+            }
+            assert_eq!(chunked_addresses, new_addresses);
+        }
+    }
+
+    #[test]
+    fn test_extend_instructions_from_iter() {
+        let lookup_table_address = Pubkey::new_unique();
+        let authority_address = Pubkey::new_unique();
+        let payer_address = Pubkey::new_unique();
+
+        let mut new_addresses = Vec::with_capacity(100);
+        new_addresses.resize_with(100, Pubkey::new_unique);
+
+        let instructions = extend_instructions_from_iter(
+            lookup_table_address,
+            authority_address,
+            Some(payer_address),
+            new_addresses.iter().copied(),
+        );
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(
+            instructions,
+            extend_lookup_table_chunked(
+                lookup_table_address,
+                authority_address,
+                Some(payer_address),
+                new_addresses,
+            )
+        );
+
+        assert!(extend_instructions_from_iter(
+            lookup_table_address,
+            authority_address,
+            None,
+            std::iter::empty(),
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_create_and_extend_lookup_table() {
+        let authority_address = Pubkey::new_unique();
+        let payer_address = Pubkey::new_unique();
+        let mut initial_addresses = Vec::with_capacity(40);
+        initial_addresses.resize_with(40, Pubkey::new_unique);
+
+        let (instructions, lookup_table_address) = create_and_extend_lookup_table(
+            authority_address,
+            payer_address,
+            123,
+            initial_addresses.clone(),
+        );
+
+        let (create_instruction, expected_lookup_table_address) =
+            create_lookup_table(authority_address, payer_address, 123);
+        assert_eq!(lookup_table_address, expected_lookup_table_address);
+
+        let mut expected_instructions = vec![create_instruction];
+        expected_instructions.extend(extend_lookup_table_chunked(
+            lookup_table_address,
+            authority_address,
+            Some(payer_address),
+            initial_addresses,
+        ));
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(instructions, expected_instructions);
+
+        // Without initial addresses, only the table is created.
+        let (instructions, _) =
+            create_and_extend_lookup_table(authority_address, payer_address, 123, vec![]);
+        assert_eq!(instructions, expected_instructions[..1]);
+    }
+
+    #[test]
+    fn test_create_lookup_table_idempotent_with_bump() {
+        let authority_address = Pubkey::new_unique();
+        let payer_address = Pubkey::new_unique();
+        let recent_slot = 123;
+
+        let (instruction, lookup_table_address, bump_seed) =
+            create_lookup_table_idempotent_with_bump(authority_address, payer_address, recent_slot);
+
+        assert_eq!(
+            (lookup_table_address, bump_seed),
+            derive_lookup_table_address(&authority_address, recent_slot),
+        );
+        assert_eq!(
+            (instruction, lookup_table_address),
+            create_lookup_table(authority_address, payer_address, recent_slot),
+        );
+        assert_eq!(
+            parse_instruction(
+                &create_lookup_table_idempotent(authority_address, payer_address, recent_slot)
+                    .0
+                    .data
+            ),
+            Ok(AddressLookupTableInstruction::CreateLookupTable {
+                recent_slot,
+                bump_seed
+            }),
+        );
+    }
+
+    #[test]
+    fn test_derive_lookup_table_addresses_for_slots() {
+        let authority_address = Pubkey::new_unique();
+
+        let derived = derive_lookup_table_addresses_for_slots(&authority_address, 100..110);
+        assert_eq!(derived.len(), 10);
+        for (recent_block_slot, lookup_table_address, bump_seed) in &derived {
+            assert_eq!(
+                derive_lookup_table_address(&authority_address, *recent_block_slot),
+                (*lookup_table_address, *bump_seed),
+            );
+        }
+        assert_eq!(
+            derived.iter().map(|(slot, _, _)| *slot).collect::<Vec<_>>(),
+            (100..110).collect::<Vec<_>>(),
+        );
+
+        // Derivation is deterministic.
+        assert_eq!(
+            derive_lookup_table_addresses_for_slots(&authority_address, 100..110),
+            derived,
+        );
+        assert!(derive_lookup_table_addresses_for_slots(&authority_address, []).is_empty());
+    }
+
+    #[test]
+    fn test_is_recent_slot() {
+        let slot_hash_slots = [105, 104, 102, 101];
+
+        assert!(is_recent_slot(105, &slot_hash_slots));
+        assert!(is_recent_slot(101, &slot_hash_slots));
+        // Skipped slots are not present.
+        assert!(!is_recent_slot(103, &slot_hash_slots));
+        // Neither are slots outside the window.
+        assert!(!is_recent_slot(100, &slot_hash_slots));
+        assert!(!is_recent_slot(106, &slot_hash_slots));
+        assert!(!is_recent_slot(105, &[]));
+    }
+
+    #[test]
+    fn test_find_lookup_table_address_with_all_bumps() {
+        let authority_address = Pubkey::new_unique();
+        let recent_block_slot = 123;
+
+        let all_bumps =
+            find_lookup_table_address_with_all_bumps(&authority_address, recent_block_slot);
+        assert!(all_bumps.len() > 1);
+        assert_eq!(
+            all_bumps[0],
+            derive_lookup_table_address(&authority_address, recent_block_slot)
+        );
+        assert!(all_bumps.windows(2).all(|pair| pair[0].1 > pair[1].1));
+        for (lookup_table_address, bump) in &all_bumps {
+            assert_eq!(
+                create_lookup_table_address(&authority_address, recent_block_slot, *bump),
+                Ok(*lookup_table_address)
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_lookup_table_address_bump() {
+        let authority_address = Pubkey::new_unique();
+        let recent_block_slot = 123;
+
+        let all_bumps =
+            find_lookup_table_address_with_all_bumps(&authority_address, recent_block_slot);
+
+        // Canonical address.
+        let (canonical_address, canonical_bump) = all_bumps[0];
+        assert_eq!(
+            find_lookup_table_address_bump(
+                &canonical_address,
+                &authority_address,
+                recent_block_slot
+            ),
+            Some(canonical_bump)
+        );
+
+        // Non-canonical address.
+        let (non_canonical_address, non_canonical_bump) = all_bumps[1];
+        assert_eq!(
+            find_lookup_table_address_bump(
+                &non_canonical_address,
+                &authority_address,
+                recent_block_slot
+            ),
+            Some(non_canonical_bump)
+        );
+
+        // Address derived from a different slot or authority.
+        assert_eq!(
+            find_lookup_table_address_bump(
+                &canonical_address,
+                &authority_address,
+                recent_block_slot + 1
+            ),
+            None
+        );
+        assert_eq!(
+            find_lookup_table_address_bump(
+                &canonical_address,
+                &Pubkey::new_unique(),
+                recent_block_slot
+            ),
+            None
+        );
+
+        // Arbitrary address.
+        assert_eq!(
+            find_lookup_table_address_bump(
+                &Pubkey::new_unique(),
+                &authority_address,
+                recent_block_slot
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_recommended_recent_slot() {
+        assert_eq!(recommended_recent_slot(1_000), 992);
+        assert_eq!(recommended_recent_slot(8), 0);
+        // Saturates near the first slot.
+        assert_eq!(recommended_recent_slot(7), 0);
+        assert_eq!(recommended_recent_slot(0), 0);
+    }
+
+    #[test]
+    fn test_create_lookup_table_address() {
+        let authority_address = Pubkey::new_unique();
+        let mut checked_invalid_bump = false;
+
+        for recent_block_slot in 0..32 {
+            let (lookup_table_address, bump_seed) =
+                derive_lookup_table_address(&authority_address, recent_block_slot);
+
+            assert_eq!(
+                create_lookup_table_address(&authority_address, recent_block_slot, bump_seed),
+                Ok(lookup_table_address),
+            );
+
+            // Every bump above the canonical one was rejected while searching
+            // for it, since it produces an address on the ed25519 curve.
+            for bump in (bump_seed..=u8::MAX).skip(1) {
+                assert_eq!(
+                    create_lookup_table_address(&authority_address, recent_block_slot, bump),
+                    Err(PubkeyError::InvalidSeeds),
+                );
+                checked_invalid_bump = true;
+            }
+        }
+
+        assert!(checked_invalid_bump);
+    }
+
+    #[test]
+    fn test_create_lookup_table_with_bump() {
+        let authority_address = Pubkey::new_unique();
+        let payer_address = Pubkey::new_unique();
+        let mut checked_invalid_bump = false;
+
+        for recent_slot in 0..32 {
+            let (_, bump_seed) = derive_lookup_table_address(&authority_address, recent_slot);
+
+            assert_eq!(
+                create_lookup_table_with_bump(
+                    authority_address,
+                    payer_address,
+                    recent_slot,
+                    bump_seed
+                ),
+                Ok(create_lookup_table(
+                    authority_address,
+                    payer_address,
+                    recent_slot
+                )),
+            );
+
+            for bump in (bump_seed..=u8::MAX).skip(1) {
+                assert_eq!(
+                    create_lookup_table_with_bump(
+                        authority_address,
+                        payer_address,
+                        recent_slot,
+                        bump
+                    ),
+                    Err(PubkeyError::InvalidSeeds),
+                );
+                checked_invalid_bump = true;
+            }
+        }
+
+        assert!(checked_invalid_bump);
+    }
+
+    #[test]
+    fn test_close_lookup_tables() {
+        let lookup_table_addresses = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let authority_address = Pubkey::new_unique();
+        let recipient_address = Pubkey::new_unique();
+
+        let instructions = close_lookup_tables(
+            &lookup_table_addresses,
+            authority_address,
+            recipient_address,
+        );
+
+        assert_eq!(instructions.len(), 3);
+        for (instruction, lookup_table_address) in
+            instructions.iter().zip(lookup_table_addresses.iter())
+        {
+            assert_eq!(instruction.program_id, crate::id());
+            assert_eq!(
+                instruction.accounts,
+                vec![
+                    AccountMeta::new(*lookup_table_address, false),
+                    AccountMeta::new_readonly(authority_address, true),
+                    AccountMeta::new(recipient_address, false),
+                ],
+            );
+            assert_eq!(
+                parse_instruction(&instruction.data),
+                Ok(AddressLookupTableInstruction::CloseLookupTable),
+            );
+        }
+
+        assert!(close_lookup_tables(&[], authority_address, recipient_address).is_empty());
+    }
+
+    #[test]
+    fn test_deactivate_lookup_table_instructions() {
+        let lookup_table_address = Pubkey::new_unique();
+        let authority_address = Pubkey::new_unique();
+        let recipient_address = Pubkey::new_unique();
+
+        let (deactivate_instruction, close_instruction) = deactivate_lookup_table_instructions(
+            lookup_table_address,
+            authority_address,
+            recipient_address,
+        );
+
+        assert_eq!(deactivate_instruction.program_id, crate::id());
+        assert_eq!(
+            deactivate_instruction.accounts,
+            vec![
+                AccountMeta::new(lookup_table_address, false),
+                AccountMeta::new_readonly(authority_address, true),
+            ],
+        );
+        assert_eq!(
+            parse_instruction(&deactivate_instruction.data),
+            Ok(AddressLookupTableInstruction::DeactivateLookupTable),
+        );
+
+        assert_eq!(close_instruction.program_id, crate::id());
+        assert_eq!(
+            close_instruction.accounts,
+            vec![
+                AccountMeta::new(lookup_table_address, false),
+                AccountMeta::new_readonly(authority_address, true),
+                AccountMeta::new(recipient_address, false),
+            ],
+        );
+        assert_eq!(
+            parse_instruction(&close_instruction.data),
+            Ok(AddressLookupTableInstruction::CloseLookupTable),
+        );
+    }
+
+    #[test]
+    fn test_create_lookup_table_with_params() {
+        let authority = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let recent_slot = 123;
+
+        assert_eq!(
+            create_lookup_table_with_params(CreateLookupTableParams {
+                authority,
+                payer,
+                recent_slot,
+            }),
+            create_lookup_table(authority, payer, recent_slot),
+        );
+    }
+
+    #[test]
+    fn test_rotate_lookup_table_instructions() {
+        let old_lookup_table_address = Pubkey::new_unique();
+        let authority_address = Pubkey::new_unique();
+        let payer_address = Pubkey::new_unique();
+        let recipient_address = Pubkey::new_unique();
+        let recent_slot = 123;
+
+        let (instructions, new_lookup_table_address) = rotate_lookup_table_instructions(
+            old_lookup_table_address,
+            authority_address,
+            recent_slot,
+            payer_address,
+            recipient_address,
+        );
+
+        let (create_instruction, expected_address) =
+            create_lookup_table(authority_address, payer_address, recent_slot);
+        assert_eq!(new_lookup_table_address, expected_address);
+        assert_eq!(
+            instructions,
+            vec![
+                create_instruction,
+                deactivate_lookup_table(old_lookup_table_address, authority_address),
+                close_lookup_table(
+                    old_lookup_table_address,
+                    authority_address,
+                    recipient_address
+                ),
+            ]
+        );
+    }
+}
@@ -1,11 +1,16 @@
 //! Address Lookup Table Program
 #![cfg_attr(feature = "frozen-abi", feature(min_specialization))]
 
+#[cfg(feature = "client")]
+pub mod client;
+pub mod cost;
 #[cfg(all(target_os = "solana", feature = "bpf-entrypoint"))]
 mod entrypoint;
 pub mod error;
 pub mod instruction;
 pub mod processor;
 pub mod state;
+#[cfg(all(feature = "test-utils", not(target_os = "solana")))]
+pub mod test_utils;
 
 solana_program::declare_id!("AddressLookupTab1e1111111111111111111111111");
@@ -4,9 +4,10 @@ use {
     crate::{
         check_id,
         error::AddressLookupTableError,
-        instruction::AddressLookupTableInstruction,
+        instruction::{parse_instruction, AddressLookupTableInstruction},
         state::{
-            AddressLookupTable, ProgramState, LOOKUP_TABLE_MAX_ADDRESSES, LOOKUP_TABLE_META_SIZE,
+            AddressLookupTable, LookupTableMeta, LookupTableStatus, ProgramState,
+            LOOKUP_TABLE_MAX_ADDRESSES, LOOKUP_TABLE_META_SIZE,
         },
     },
     solana_program::{
@@ -18,106 +19,147 @@ use {
         program_error::ProgramError,
         pubkey::{Pubkey, PUBKEY_BYTES},
         rent::Rent,
-        slot_hashes::MAX_ENTRIES,
-        system_instruction,
+        system_instruction, system_program,
         sysvar::{slot_hashes::SlotHashesSysvar, Sysvar},
     },
 };
 
-/// Activation status of a lookup table
-#[derive(Debug, PartialEq, Eq, Clone)]
-enum LookupTableStatus {
-    Activated,
-    Deactivating { remaining_blocks: usize },
-    Deactivated,
-}
-
-// Return the current status of the lookup table
-fn get_lookup_table_status(
+// Return the position of a lookup table's deactivation slot in the
+// `SlotHashes` sysvar, used to determine the table's status.
+fn get_deactivation_slot_position(
     deactivation_slot: Slot,
     current_slot: Slot,
-) -> Result<LookupTableStatus, ProgramError> {
-    if deactivation_slot == Slot::MAX {
-        Ok(LookupTableStatus::Activated)
-    } else if deactivation_slot == current_slot {
-        Ok(LookupTableStatus::Deactivating {
-            remaining_blocks: MAX_ENTRIES.saturating_add(1),
-        })
-    } else if let Some(slot_position) = SlotHashesSysvar::position(&deactivation_slot)
-        .map_err(|_| ProgramError::UnsupportedSysvar)?
-    {
-        // Deactivation requires a cool-down period to give in-flight transactions
-        // enough time to land and to remove indeterminism caused by transactions
-        // loading addresses in the same slot when a table is closed. The
-        // cool-down period is equivalent to the amount of time it takes for
-        // a slot to be removed from the slot hash list.
-        //
-        // By using the slot hash to enforce the cool-down, there is a side effect
-        // of not allowing lookup tables to be recreated at the same derived address
-        // because tables must be created at an address derived from a recent slot.
-        Ok(LookupTableStatus::Deactivating {
-            remaining_blocks: MAX_ENTRIES.saturating_sub(slot_position),
-        })
+) -> Result<Option<usize>, ProgramError> {
+    // Only tables deactivated in a prior slot need to be located in the
+    // `SlotHashes` sysvar.
+    if deactivation_slot == Slot::MAX || deactivation_slot == current_slot {
+        Ok(None)
     } else {
-        Ok(LookupTableStatus::Deactivated)
+        SlotHashesSysvar::position(&deactivation_slot).map_err(|_| ProgramError::UnsupportedSysvar)
+    }
+}
+
+// Returns whether the `SlotHashes` sysvar has an entry for the provided slot.
+fn slot_hashes_contains(slot: &Slot) -> Result<bool, ProgramError> {
+    SlotHashesSysvar::get(slot)
+        .map(|hash| hash.is_some())
+        .map_err(|_| ProgramError::UnsupportedSysvar)
+}
+
+// Reject writes to a read-only lookup table account, mirroring the check the
+// `TransactionContext` performs for builtin programs.
+fn ensure_table_writable(lookup_table_info: &AccountInfo) -> ProgramResult {
+    if !lookup_table_info.is_writable {
+        return Err(AddressLookupTableError::ReadonlyDataModified.into());
     }
+    Ok(())
 }
 
-// Maximum input buffer length that can be deserialized.
-// See `solana_sdk::packet::PACKET_DATA_SIZE`.
-const MAX_INPUT_LEN: usize = 1232;
-// Maximum vector length for new keys to be appended to a lookup table,
-// provided to the `ExtendLookupTable` instruction.
-// See comments below for `safe_deserialize_instruction`.
-//
-// Take the maximum input length and subtract 4 bytes for the discriminator,
-// 8 bytes for the vector length, then divide that by the size of a `Pubkey`.
-const MAX_NEW_KEYS_VECTOR_LEN: usize = (MAX_INPUT_LEN - 4 - 8) / 32;
-
-// Stub of `AddressLookupTableInstruction` for partial deserialization.
-// Keep in sync with the program's instructions in `instructions`.
-#[allow(clippy::enum_variant_names)]
-#[cfg_attr(test, derive(strum_macros::EnumIter))]
-#[derive(serde::Serialize, serde::Deserialize, PartialEq)]
-enum InstructionStub {
-    CreateLookupTable,
-    FreezeLookupTable,
-    ExtendLookupTable { vector_len: u64 },
-    DeactivateLookupTable,
-    CloseLookupTable,
+// Reject anything other than the System program before invoking it, rather
+// than surfacing a confusing inner instruction error from the CPI.
+fn ensure_system_program(system_program_info: &AccountInfo) -> ProgramResult {
+    if !system_program::check_id(system_program_info.key) {
+        msg!("Incorrect System program account");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
 }
 
-// [Core BPF]: The original Address Lookup Table builtin leverages the
-// `solana_sdk::program_utils::limited_deserialize` method to cap the length of
-// the input buffer at `MAX_INPUT_LEN` (1232). As a result, any input buffer
-// larger than `MAX_INPUT_LEN` will abort deserialization and return
-// `InstructionError::InvalidInstructionData`.
-//
-// Howevever, since `ExtendLookupTable` contains a vector of `Pubkey`, the
-// `limited_deserialize` method will still read the vector's length and attempt
-// to allocate a vector of the designated size. For extremely large length
-// values, this can cause the initial allocation of a large vector to exhuast
-// the BPF program's heap before deserialization can proceed.
-//
-// To mitigate this memory issue, the BPF version of the program has been
-// designed to "peek" the length value for `ExtendLookupTable`, and ensure it
-// cannot allocate a vector that would otherwise violate the input buffer
-// length restriction.
-fn safe_deserialize_instruction(
-    input: &[u8],
-) -> Result<AddressLookupTableInstruction, ProgramError> {
-    match bincode::deserialize::<InstructionStub>(input)
-        .map_err(|_| ProgramError::InvalidInstructionData)?
+// Check that the instruction was given at least as many accounts as its
+// processor always reads, so a missing account is reported before any other
+// validation. Accounts which are only read conditionally are not counted:
+// the system program for create, which is skipped for existing tables, and
+// the payer and system program for extend, which are skipped for prepaid
+// tables.
+fn validate_account_count(
+    instruction: &AddressLookupTableInstruction,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let min_accounts = match instruction {
+        AddressLookupTableInstruction::FreezeLookupTable
+        | AddressLookupTableInstruction::ExtendLookupTable { .. }
+        | AddressLookupTableInstruction::DeactivateLookupTable
+        | AddressLookupTableInstruction::ReactivateLookupTable
+        | AddressLookupTableInstruction::ExtendLookupTableDedup { .. } => 2,
+        AddressLookupTableInstruction::CreateLookupTable { .. }
+        | AddressLookupTableInstruction::CloseLookupTable
+        | AddressLookupTableInstruction::TransferAuthority
+        | AddressLookupTableInstruction::TrimLookupTable { .. }
+        | AddressLookupTableInstruction::CloseLookupTableAndReassign => 3,
+    };
+    if accounts.len() < min_accounts {
+        msg!(
+            "Expected at least {} accounts, but only {} were provided",
+            min_accounts,
+            accounts.len()
+        );
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    Ok(())
+}
+
+// Check that the lookup table is owned by the program and that the signing
+// authority may modify it, returning the table's metadata.
+fn load_and_authorize(
+    program_id: &Pubkey,
+    lookup_table_info: &AccountInfo,
+    authority_info: &AccountInfo,
+) -> Result<LookupTableMeta, ProgramError> {
+    check_owner_and_signer(program_id, lookup_table_info, authority_info)?;
+    load_authorized_meta(lookup_table_info, authority_info)
+}
+
+// The account checks of `load_and_authorize`, for handlers which must run
+// their own checks before the table's metadata is read.
+fn check_owner_and_signer(
+    program_id: &Pubkey,
+    lookup_table_info: &AccountInfo,
+    authority_info: &AccountInfo,
+) -> ProgramResult {
+    if lookup_table_info.owner != program_id {
+        msg!("Lookup table owner should be the Address Lookup Table program");
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if !authority_info.is_signer {
+        msg!("Authority account must be a signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    Ok(())
+}
+
+// The metadata checks of `load_and_authorize`. Assumes the account checks
+// have already passed.
+fn load_authorized_meta(
+    lookup_table_info: &AccountInfo,
+    authority_info: &AccountInfo,
+) -> Result<LookupTableMeta, ProgramError> {
+    let lookup_table_data = lookup_table_info.try_borrow_data()?;
+    // Only the metadata is needed, so read it directly rather than
+    // deserializing the full table.
+    let lookup_table_meta = AddressLookupTable::deserialize_meta_view(&lookup_table_data)?;
+    // Mirror the address data validation of `AddressLookupTable::deserialize`.
+    if lookup_table_data
+        .len()
+        .saturating_sub(LOOKUP_TABLE_META_SIZE)
+        .checked_rem(PUBKEY_BYTES)
+        != Some(0)
     {
-        InstructionStub::ExtendLookupTable { vector_len }
-            if vector_len as usize > MAX_NEW_KEYS_VECTOR_LEN =>
-        {
-            return Err(ProgramError::InvalidInstructionData);
-        }
-        _ => {}
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let authority = lookup_table_meta.authority();
+    if authority.is_none() {
+        msg!("Lookup table is frozen");
+        return Err(ProgramError::Immutable);
     }
-    solana_program::program_utils::limited_deserialize(input, MAX_INPUT_LEN as u64)
-        .map_err(|_| ProgramError::InvalidInstructionData)
+    if authority != Some(*authority_info.key) {
+        msg!("Incorrect lookup table authority");
+        return Err(ProgramError::IncorrectAuthority);
+    }
+
+    Ok(lookup_table_meta.to_meta())
 }
 
 // [Core BPF]: Feature "FKAcEvNgSY79RpqsPNUV5gDyumopH4cEHqUxyfm8b8Ap"
@@ -145,10 +187,7 @@ fn process_create_lookup_table(
     }
 
     let derivation_slot = {
-        if SlotHashesSysvar::get(&untrusted_recent_slot)
-            .map_err(|_| ProgramError::UnsupportedSysvar)?
-            .is_some()
-        {
+        if slot_hashes_contains(&untrusted_recent_slot)? {
             Ok(untrusted_recent_slot)
         } else {
             msg!("{} is not a recent slot", untrusted_recent_slot);
@@ -189,6 +228,9 @@ fn process_create_lookup_table(
         .max(1)
         .saturating_sub(lookup_table_info.lamports());
 
+    let system_program_info = next_account_info(accounts_iter)?;
+    ensure_system_program(system_program_info)?;
+
     if required_lamports > 0 {
         invoke(
             &system_instruction::transfer(payer_info.key, lookup_table_info.key, required_lamports),
@@ -196,8 +238,6 @@ fn process_create_lookup_table(
         )?;
     }
 
-    let _system_program_info = next_account_info(accounts_iter)?;
-
     invoke_signed(
         &system_instruction::allocate(lookup_table_info.key, lookup_table_data_len as u64),
         &[lookup_table_info.clone()],
@@ -222,44 +262,22 @@ fn process_freeze_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) ->
     let accounts_iter = &mut accounts.iter();
 
     let lookup_table_info = next_account_info(accounts_iter)?;
-
-    if lookup_table_info.owner != program_id {
-        msg!("Lookup table owner should be the Address Lookup Table program");
-        return Err(ProgramError::InvalidAccountOwner);
-    }
-
     let authority_info = next_account_info(accounts_iter)?;
 
-    if !authority_info.is_signer {
-        msg!("Authority account must be a signer");
-        return Err(ProgramError::MissingRequiredSignature);
-    }
-
-    let mut lookup_table_meta = {
-        let lookup_table_data = lookup_table_info.try_borrow_data()?;
-        let lookup_table = AddressLookupTable::deserialize(&lookup_table_data)?;
+    let mut lookup_table_meta = load_and_authorize(program_id, lookup_table_info, authority_info)?;
 
-        if lookup_table.meta.authority.is_none() {
-            msg!("Lookup table is already frozen");
-            return Err(ProgramError::Immutable);
-        }
-        if lookup_table.meta.authority != Some(*authority_info.key) {
-            msg!("Incorrect lookup table authority");
-            return Err(ProgramError::IncorrectAuthority);
-        }
-        if lookup_table.meta.deactivation_slot != Slot::MAX {
-            msg!("Deactivated tables cannot be frozen");
-            return Err(ProgramError::InvalidArgument);
-        }
-        if lookup_table.addresses.is_empty() {
-            msg!("Empty lookup tables cannot be frozen");
-            return Err(ProgramError::InvalidInstructionData);
-        }
-
-        lookup_table.meta
-    };
+    if lookup_table_meta.is_deactivated() {
+        msg!("Deactivated tables cannot be frozen");
+        return Err(ProgramError::InvalidArgument);
+    }
+    if lookup_table_info.data_len() == LOOKUP_TABLE_META_SIZE {
+        msg!("Empty lookup tables cannot be frozen");
+        return Err(ProgramError::InvalidInstructionData);
+    }
 
     lookup_table_meta.authority = None;
+
+    ensure_table_writable(lookup_table_info)?;
     AddressLookupTable::overwrite_meta_data(
         &mut lookup_table_info.try_borrow_mut_data()?[..],
         lookup_table_meta,
@@ -271,43 +289,33 @@ fn process_freeze_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) ->
 fn process_extend_lookup_table(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    new_addresses: Vec<Pubkey>,
+    mut new_addresses: Vec<Pubkey>,
+    deduplicate: bool,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
 
     let lookup_table_info = next_account_info(accounts_iter)?;
-
-    if lookup_table_info.owner != program_id {
-        msg!("Lookup table owner should be the Address Lookup Table program");
-        return Err(ProgramError::InvalidAccountOwner);
-    }
-
     let authority_info = next_account_info(accounts_iter)?;
 
-    if !authority_info.is_signer {
-        msg!("Authority account must be a signer");
-        return Err(ProgramError::MissingRequiredSignature);
+    let mut lookup_table_meta = load_and_authorize(program_id, lookup_table_info, authority_info)?;
+
+    if lookup_table_meta.is_deactivated() {
+        msg!("Deactivated tables cannot be extended");
+        return Err(ProgramError::InvalidArgument);
     }
 
-    let (lookup_table_meta, new_addresses_start_index, new_table_data_len) = {
+    let (new_addresses_start_index, new_table_data_len) = {
         let lookup_table_data = lookup_table_info.try_borrow_data()?;
-        let mut lookup_table = AddressLookupTable::deserialize(&lookup_table_data)?;
-
-        if lookup_table.meta.authority.is_none() {
-            msg!("Lookup table is frozen");
-            return Err(ProgramError::Immutable);
-        }
-        if lookup_table.meta.authority != Some(*authority_info.key) {
-            msg!("Incorrect lookup table authority");
-            return Err(ProgramError::IncorrectAuthority);
-        }
-        if lookup_table.meta.deactivation_slot != Slot::MAX {
-            msg!("Deactivated tables cannot be extended");
-            return Err(ProgramError::InvalidArgument);
-        }
-        if lookup_table.addresses.len() >= LOOKUP_TABLE_MAX_ADDRESSES {
+        // The metadata was validated above, so only the addresses are
+        // needed.
+        let addresses: &[Pubkey] = lookup_table_data
+            .get(LOOKUP_TABLE_META_SIZE..)
+            .and_then(|raw_addresses_data| bytemuck::try_cast_slice(raw_addresses_data).ok())
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        if addresses.len() >= LOOKUP_TABLE_MAX_ADDRESSES {
             msg!("Lookup table is full and cannot contain more addresses");
-            return Err(ProgramError::InvalidArgument);
+            return Err(AddressLookupTableError::LookupTableFull.into());
         }
 
         if new_addresses.is_empty() {
@@ -315,10 +323,29 @@ fn process_extend_lookup_table(
             return Err(ProgramError::InvalidInstructionData);
         }
 
-        let new_table_addresses_len = lookup_table
-            .addresses
+        if deduplicate {
+            // A linear scan is cheaper than hashing for tables of at most
+            // `LOOKUP_TABLE_MAX_ADDRESSES` entries.
+            let mut unique_addresses = Vec::with_capacity(new_addresses.len());
+            for address in new_addresses {
+                if !addresses.contains(&address) && !unique_addresses.contains(&address) {
+                    unique_addresses.push(address);
+                }
+            }
+            if unique_addresses.is_empty() {
+                msg!("All provided addresses are already in the lookup table");
+                return Err(AddressLookupTableError::NoNewAddresses.into());
+            }
+            new_addresses = unique_addresses;
+        }
+
+        // Both lengths are bounded (by the table size and the instruction
+        // input limit), so this can't overflow in practice, but any
+        // overflow must surface as an error rather than being clamped.
+        let new_table_addresses_len = addresses
             .len()
-            .saturating_add(new_addresses.len());
+            .checked_add(new_addresses.len())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
 
         if new_table_addresses_len > LOOKUP_TABLE_MAX_ADDRESSES {
             msg!(
@@ -326,30 +353,36 @@ fn process_extend_lookup_table(
                 new_table_addresses_len,
                 LOOKUP_TABLE_MAX_ADDRESSES,
             );
-            return Err(ProgramError::InvalidInstructionData);
+            return Err(AddressLookupTableError::ExtendExceedsCapacity.into());
         }
 
-        let old_table_addresses_len = u8::try_from(lookup_table.addresses.len()).map_err(|_| {
-            // This is impossible as long as the length of new_addresses
-            // is non-zero and LOOKUP_TABLE_MAX_ADDRESSES == u8::MAX + 1.
+        // The start index is stored as a `u8`. The capacity checks above
+        // already reject a full table, so this conversion cannot fail while
+        // `LOOKUP_TABLE_MAX_ADDRESSES == u8::MAX + 1`. The guard is defensive,
+        // in case those checks or the maximum ever change.
+        let old_table_addresses_len = u8::try_from(addresses.len()).map_err(|_| {
+            msg!(
+                "Lookup table length {} exceeds the maximum start index of {}",
+                addresses.len(),
+                u8::MAX,
+            );
             ProgramError::InvalidAccountData
         })?;
 
         let clock = <Clock as Sysvar>::get()?;
-        if clock.slot != lookup_table.meta.last_extended_slot {
-            lookup_table.meta.last_extended_slot = clock.slot;
-            lookup_table.meta.last_extended_slot_start_index = old_table_addresses_len;
+        if clock.slot != lookup_table_meta.last_extended_slot {
+            lookup_table_meta.last_extended_slot = clock.slot;
+            lookup_table_meta.last_extended_slot_start_index = old_table_addresses_len;
         }
 
-        let new_table_data_len = LOOKUP_TABLE_META_SIZE
-            .checked_add(new_table_addresses_len.saturating_mul(PUBKEY_BYTES))
+        // At most `LOOKUP_TABLE_META_SIZE + LOOKUP_TABLE_MAX_ADDRESSES *
+        // PUBKEY_BYTES` after the capacity check above.
+        let new_table_data_len = new_table_addresses_len
+            .checked_mul(PUBKEY_BYTES)
+            .and_then(|addresses_len| LOOKUP_TABLE_META_SIZE.checked_add(addresses_len))
             .ok_or(ProgramError::ArithmeticOverflow)?;
 
-        (
-            lookup_table.meta,
-            old_table_addresses_len,
-            new_table_data_len,
-        )
+        (old_table_addresses_len, new_table_data_len)
     };
 
     // [Core BPF]:
@@ -379,10 +412,12 @@ fn process_extend_lookup_table(
     //
     // Since the account can never be executable and also owned by the ALT
     // program, we'll just focus on readonly.
-    if !lookup_table_info.is_writable {
-        return Err(AddressLookupTableError::ReadonlyDataModified.into());
-    }
+    ensure_table_writable(lookup_table_info)?;
 
+    // The metadata is overwritten before the account is grown, as in the
+    // builtin. This is safe even if a later step fails: the runtime discards
+    // every account modification made by a failed instruction, so the table
+    // can never be left with metadata that is inconsistent with its size.
     AddressLookupTable::overwrite_meta_data(
         &mut lookup_table_info.try_borrow_mut_data()?[..],
         lookup_table_meta,
@@ -399,6 +434,15 @@ fn process_extend_lookup_table(
         uninitialized_addresses.copy_from_slice(&new_addresses);
     }
 
+    // Tables may be prepaid for some or all of the new addresses, so the
+    // payer is only required, and only charged, for any shortfall.
+    //
+    // The subtraction saturates on purpose: a table already holding at
+    // least the rent-exempt minimum has no shortfall. It can't hide an
+    // underfunded table, since any balance below the minimum yields the
+    // exact difference. The minimum itself is computed from a data length
+    // bounded above, far from the range where `Rent::minimum_balance`
+    // would lose precision.
     let rent = <Rent as Sysvar>::get()?;
     let required_lamports = rent
         .minimum_balance(new_table_data_len)
@@ -406,14 +450,21 @@ fn process_extend_lookup_table(
         .saturating_sub(lookup_table_info.lamports());
 
     if required_lamports > 0 {
-        let payer_info = next_account_info(accounts_iter)?;
+        let payer_info = next_account_info(accounts_iter).map_err(|_| {
+            msg!(
+                "Lookup table requires {} more lamports to be rent exempt, but no payer was provided",
+                required_lamports
+            );
+            AddressLookupTableError::PayerRequiredForExtend
+        })?;
 
         if !payer_info.is_signer {
             msg!("Payer account must be a signer");
             return Err(ProgramError::MissingRequiredSignature);
         }
 
-        let _system_program_info = next_account_info(accounts_iter)?;
+        let system_program_info = next_account_info(accounts_iter)?;
+        ensure_system_program(system_program_info)?;
 
         invoke(
             &system_instruction::transfer(payer_info.key, lookup_table_info.key, required_lamports),
@@ -428,43 +479,20 @@ fn process_deactivate_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]
     let accounts_iter = &mut accounts.iter();
 
     let lookup_table_info = next_account_info(accounts_iter)?;
-
-    if lookup_table_info.owner != program_id {
-        msg!("Lookup table owner should be the Address Lookup Table program");
-        return Err(ProgramError::InvalidAccountOwner);
-    }
-
     let authority_info = next_account_info(accounts_iter)?;
 
-    if !authority_info.is_signer {
-        msg!("Authority account must be a signer");
-        return Err(ProgramError::MissingRequiredSignature);
-    }
+    let mut lookup_table_meta = load_and_authorize(program_id, lookup_table_info, authority_info)?;
 
-    let mut lookup_table_meta = {
-        let lookup_table_data = lookup_table_info.try_borrow_data()?;
-        let lookup_table = AddressLookupTable::deserialize(&lookup_table_data)?;
-
-        if lookup_table.meta.authority.is_none() {
-            msg!("Lookup table is frozen");
-            return Err(ProgramError::Immutable);
-        }
-        if lookup_table.meta.authority != Some(*authority_info.key) {
-            msg!("Incorrect lookup table authority");
-            return Err(ProgramError::IncorrectAuthority);
-        }
-        if lookup_table.meta.deactivation_slot != Slot::MAX {
-            msg!("Lookup table is already deactivated");
-            return Err(ProgramError::InvalidArgument);
-        }
-
-        lookup_table.meta
-    };
+    if lookup_table_meta.is_deactivated() {
+        msg!("Lookup table is already deactivated");
+        return Err(ProgramError::InvalidArgument);
+    }
 
     let clock = <Clock as Sysvar>::get()?;
 
     lookup_table_meta.deactivation_slot = clock.slot;
 
+    ensure_table_writable(lookup_table_info)?;
     AddressLookupTable::overwrite_meta_data(
         &mut lookup_table_info.try_borrow_mut_data()?[..],
         lookup_table_meta,
@@ -473,22 +501,17 @@ fn process_deactivate_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]
     Ok(())
 }
 
-fn process_close_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+fn process_close_lookup_table(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    reassign: bool,
+) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
 
     let lookup_table_info = next_account_info(accounts_iter)?;
-
-    if lookup_table_info.owner != program_id {
-        msg!("Lookup table owner should be the Address Lookup Table program");
-        return Err(ProgramError::InvalidAccountOwner);
-    }
-
     let authority_info = next_account_info(accounts_iter)?;
 
-    if !authority_info.is_signer {
-        msg!("Authority account must be a signer");
-        return Err(ProgramError::MissingRequiredSignature);
-    }
+    check_owner_and_signer(program_id, lookup_table_info, authority_info)?;
 
     let recipient_info = next_account_info(accounts_iter)?;
 
@@ -504,41 +527,50 @@ fn process_close_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) ->
         return Err(ProgramError::InvalidArgument);
     }
 
-    {
-        let lookup_table_data = lookup_table_info.try_borrow_data()?;
-        let lookup_table = AddressLookupTable::deserialize(&lookup_table_data)?;
+    // Closed tables are not reassigned unless closed with
+    // `CloseLookupTableAndReassign`, so until the end of the transaction in
+    // which it was closed, a closed table is still owned by the program.
+    if lookup_table_info.data_is_empty() && lookup_table_info.lamports() == 0 {
+        msg!("Lookup table has already been closed");
+        return Err(AddressLookupTableError::LookupTableAlreadyClosed.into());
+    }
 
-        if lookup_table.meta.authority.is_none() {
-            msg!("Lookup table is frozen");
-            return Err(ProgramError::Immutable);
-        }
-        if lookup_table.meta.authority != Some(*authority_info.key) {
-            msg!("Incorrect lookup table authority");
-            return Err(ProgramError::IncorrectAuthority);
-        }
+    let lookup_table_meta = load_authorized_meta(lookup_table_info, authority_info)?;
 
-        let clock = <Clock as Sysvar>::get()?;
+    // The recipient may be the authority itself. The runtime backs both
+    // account infos with the same account, and only the recipient's lamports
+    // are mutably borrowed below, so no special handling is required.
 
-        match get_lookup_table_status(lookup_table.meta.deactivation_slot, clock.slot)? {
-            LookupTableStatus::Activated => {
-                msg!("Lookup table is not deactivated");
-                Err(ProgramError::InvalidArgument)
-            }
-            LookupTableStatus::Deactivating { remaining_blocks } => {
-                msg!(
-                    "Table cannot be closed until it's fully deactivated in {} blocks",
-                    remaining_blocks
-                );
-                Err(ProgramError::InvalidArgument)
-            }
-            LookupTableStatus::Deactivated => Ok(()),
-        }?;
-    }
+    let clock = <Clock as Sysvar>::get()?;
+    let slot_position =
+        get_deactivation_slot_position(lookup_table_meta.deactivation_slot, clock.slot)?;
+
+    match lookup_table_meta.status(clock.slot, slot_position) {
+        LookupTableStatus::Activated => {
+            msg!("Lookup table is not deactivated");
+            Err(ProgramError::InvalidArgument)
+        }
+        LookupTableStatus::Deactivating { remaining_blocks } => {
+            msg!(
+                "Table cannot be closed until it's fully deactivated in {} blocks",
+                remaining_blocks
+            );
+            Err(ProgramError::InvalidArgument)
+        }
+        LookupTableStatus::Deactivated => Ok(()),
+    }?;
 
     let new_recipient_lamports = lookup_table_info
         .lamports()
         .checked_add(recipient_info.lamports())
-        .ok_or::<ProgramError>(ProgramError::ArithmeticOverflow)?;
+        .ok_or_else(|| {
+            msg!(
+                "Recipient balance of {} lamports cannot receive {} lamports without overflowing",
+                recipient_info.lamports(),
+                lookup_table_info.lamports(),
+            );
+            AddressLookupTableError::CloseLamportsOverflow
+        })?;
 
     if !recipient_info.is_writable {
         return Err(AddressLookupTableError::ReadonlyLamportsChanged.into());
@@ -546,13 +578,165 @@ fn process_close_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) ->
 
     **recipient_info.try_borrow_mut_lamports()? = new_recipient_lamports;
 
-    if !lookup_table_info.is_writable {
-        return Err(AddressLookupTableError::ReadonlyDataModified.into());
-    }
+    ensure_table_writable(lookup_table_info)?;
 
-    // Lookup tables are _not_ reassigned when closed.
+    // Lookup tables are _not_ reassigned when closed, unless the caller
+    // explicitly opted in.
     lookup_table_info.realloc(0, true)?;
     **lookup_table_info.try_borrow_mut_lamports()? = 0;
+    if reassign {
+        // The runtime permits the owner change since the account's data is
+        // now empty.
+        lookup_table_info.assign(&system_program::id());
+    }
+
+    Ok(())
+}
+
+fn process_transfer_authority(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let lookup_table_info = next_account_info(accounts_iter)?;
+    let authority_info = next_account_info(accounts_iter)?;
+
+    let mut lookup_table_meta = load_and_authorize(program_id, lookup_table_info, authority_info)?;
+
+    if lookup_table_meta.is_deactivated() {
+        msg!("Deactivated tables cannot have their authority transferred");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let new_authority_info = next_account_info(accounts_iter)?;
+
+    lookup_table_meta.authority = Some(*new_authority_info.key);
+
+    ensure_table_writable(lookup_table_info)?;
+    AddressLookupTable::overwrite_meta_data(
+        &mut lookup_table_info.try_borrow_mut_data()?[..],
+        lookup_table_meta,
+    )?;
+
+    Ok(())
+}
+
+fn process_reactivate_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let lookup_table_info = next_account_info(accounts_iter)?;
+    let authority_info = next_account_info(accounts_iter)?;
+
+    let mut lookup_table_meta = load_and_authorize(program_id, lookup_table_info, authority_info)?;
+
+    let clock = <Clock as Sysvar>::get()?;
+    let slot_position =
+        get_deactivation_slot_position(lookup_table_meta.deactivation_slot, clock.slot)?;
+
+    match lookup_table_meta.status(clock.slot, slot_position) {
+        LookupTableStatus::Activated => {
+            msg!("Lookup table is not deactivated");
+            Err(ProgramError::InvalidArgument)
+        }
+        LookupTableStatus::Deactivating { .. } => Ok(()),
+        LookupTableStatus::Deactivated => {
+            msg!("Lookup table is fully deactivated and cannot be reactivated");
+            Err(ProgramError::InvalidArgument)
+        }
+    }?;
+
+    lookup_table_meta.deactivation_slot = Slot::MAX;
+
+    ensure_table_writable(lookup_table_info)?;
+    AddressLookupTable::overwrite_meta_data(
+        &mut lookup_table_info.try_borrow_mut_data()?[..],
+        lookup_table_meta,
+    )?;
+
+    Ok(())
+}
+
+fn process_trim_lookup_table(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_len: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    let lookup_table_info = next_account_info(accounts_iter)?;
+    let authority_info = next_account_info(accounts_iter)?;
+
+    let lookup_table_meta = load_and_authorize(program_id, lookup_table_info, authority_info)?;
+
+    let recipient_info = next_account_info(accounts_iter)?;
+
+    if lookup_table_info.key == recipient_info.key {
+        msg!("Lookup table cannot be the recipient of reclaimed lamports");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if lookup_table_meta.is_deactivated() {
+        msg!("Deactivated tables cannot be trimmed");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // The address data was validated above, so the address count follows
+    // from the data length.
+    let addresses_len = lookup_table_info
+        .data_len()
+        .saturating_sub(LOOKUP_TABLE_META_SIZE)
+        .checked_div(PUBKEY_BYTES)
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    if new_len as usize >= addresses_len {
+        msg!(
+            "Trimmed lookup table length {} must be less than the current length {}",
+            new_len,
+            addresses_len,
+        );
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Only addresses appended in the current slot, which are not active
+    // yet, may be trimmed. Freeing the index of an active address would
+    // let the authority extend the table with a different address at the
+    // same index, changing how already-signed transactions resolve their
+    // accounts.
+    let clock = <Clock as Sysvar>::get()?;
+    if clock.slot != lookup_table_meta.last_extended_slot
+        || new_len < lookup_table_meta.last_extended_slot_start_index
+    {
+        msg!("Only addresses appended in the current slot can be trimmed");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let new_table_data_len = LOOKUP_TABLE_META_SIZE
+        .checked_add((new_len as usize).saturating_mul(PUBKEY_BYTES))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // The metadata is unchanged: the last extension still starts at or
+    // before the new length.
+    ensure_table_writable(lookup_table_info)?;
+    lookup_table_info.realloc(new_table_data_len, false)?;
+
+    let rent = <Rent as Sysvar>::get()?;
+    let reclaimed_lamports = lookup_table_info
+        .lamports()
+        .saturating_sub(rent.minimum_balance(new_table_data_len).max(1));
+
+    if reclaimed_lamports > 0 {
+        let new_recipient_lamports = recipient_info
+            .lamports()
+            .checked_add(reclaimed_lamports)
+            .ok_or::<ProgramError>(ProgramError::ArithmeticOverflow)?;
+
+        if !recipient_info.is_writable {
+            return Err(AddressLookupTableError::ReadonlyLamportsChanged.into());
+        }
+
+        **recipient_info.try_borrow_mut_lamports()? = new_recipient_lamports;
+        **lookup_table_info.try_borrow_mut_lamports()? = lookup_table_info
+            .lamports()
+            .saturating_sub(reclaimed_lamports);
+    }
 
     Ok(())
 }
@@ -560,7 +744,8 @@ fn process_close_lookup_table(program_id: &Pubkey, accounts: &[AccountInfo]) ->
 /// Processes a
 /// `solana_programs_address_lookup_table::instruction::AddressLookupTableInstruction`
 pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
-    let instruction = safe_deserialize_instruction(input)?;
+    let instruction = parse_instruction(input)?;
+    validate_account_count(&instruction, accounts)?;
     match instruction {
         AddressLookupTableInstruction::CreateLookupTable {
             recent_slot,
@@ -575,7 +760,7 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> P
         }
         AddressLookupTableInstruction::ExtendLookupTable { new_addresses } => {
             msg!("Instruction: ExtendLookupTable");
-            process_extend_lookup_table(program_id, accounts, new_addresses)
+            process_extend_lookup_table(program_id, accounts, new_addresses, false)
         }
         AddressLookupTableInstruction::DeactivateLookupTable => {
             msg!("Instruction: DeactivateLookupTable");
@@ -583,62 +768,230 @@ pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> P
         }
         AddressLookupTableInstruction::CloseLookupTable => {
             msg!("Instruction: CloseLookupTable");
-            process_close_lookup_table(program_id, accounts)
+            process_close_lookup_table(program_id, accounts, false)
+        }
+        AddressLookupTableInstruction::TransferAuthority => {
+            msg!("Instruction: TransferAuthority");
+            process_transfer_authority(program_id, accounts)
+        }
+        AddressLookupTableInstruction::ReactivateLookupTable => {
+            msg!("Instruction: ReactivateLookupTable");
+            process_reactivate_lookup_table(program_id, accounts)
+        }
+        AddressLookupTableInstruction::TrimLookupTable { new_len } => {
+            msg!("Instruction: TrimLookupTable");
+            process_trim_lookup_table(program_id, accounts, new_len)
+        }
+        AddressLookupTableInstruction::ExtendLookupTableDedup { new_addresses } => {
+            msg!("Instruction: ExtendLookupTableDedup");
+            process_extend_lookup_table(program_id, accounts, new_addresses, true)
+        }
+        AddressLookupTableInstruction::CloseLookupTableAndReassign => {
+            msg!("Instruction: CloseLookupTableAndReassign");
+            process_close_lookup_table(program_id, accounts, true)
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use {
+        super::*,
+        solana_program::{
+            clock::Epoch,
+            entrypoint::SUCCESS,
+            hash::Hash,
+            program_stubs::{set_syscall_stubs, SyscallStubs},
+            slot_hashes::{SlotHash, SlotHashes},
+        },
+        strum::IntoEnumIterator,
+    };
 
-    fn assert_instruction_serialization(
-        stub: &InstructionStub,
-        instruction: &AddressLookupTableInstruction,
-        len: usize,
-    ) {
-        assert_eq!(
-            bincode::serialize(&stub).unwrap(),
-            bincode::serialize(&instruction).unwrap()[0..len],
-        )
+    fn check_load_and_authorize(
+        owner: &Pubkey,
+        authority_is_signer: bool,
+        mut data: Vec<u8>,
+        authority: &Pubkey,
+    ) -> Result<LookupTableMeta, ProgramError> {
+        let lookup_table_key = Pubkey::new_unique();
+        let mut lookup_table_lamports = 0;
+        let lookup_table_info = AccountInfo::new(
+            &lookup_table_key,
+            false,
+            true,
+            &mut lookup_table_lamports,
+            &mut data,
+            owner,
+            false,
+            Epoch::default(),
+        );
+        let mut authority_lamports = 0;
+        let mut authority_data = [];
+        let authority_info = AccountInfo::new(
+            authority,
+            authority_is_signer,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &system_program::ID,
+            false,
+            Epoch::default(),
+        );
+        load_and_authorize(&crate::id(), &lookup_table_info, &authority_info)
     }
 
     #[test]
-    fn test_instruction_stubs() {
+    fn test_load_and_authorize_error_ordering() {
+        let authority = Pubkey::new_unique();
+        let wrong_authority = Pubkey::new_unique();
+        let table_data = |authority: Option<Pubkey>| {
+            AddressLookupTable::from_addresses(authority, vec![Pubkey::new_unique()])
+                .serialize_for_tests()
+                .unwrap()
+        };
+        let mut misaligned_data = table_data(Some(authority));
+        misaligned_data.push(0);
+
+        // The owner is checked first.
         assert_eq!(
-            <InstructionStub as strum::IntoEnumIterator>::iter().count(),
-            <AddressLookupTableInstruction as strum::IntoEnumIterator>::iter().count(),
-        );
-
-        assert_instruction_serialization(
-            &InstructionStub::CreateLookupTable,
-            &AddressLookupTableInstruction::CreateLookupTable {
-                recent_slot: 0,
-                bump_seed: 0,
-            },
-            4,
+            check_load_and_authorize(
+                &Pubkey::new_unique(),
+                false,
+                misaligned_data.clone(),
+                &wrong_authority
+            ),
+            Err(ProgramError::InvalidAccountOwner),
         );
-        assert_instruction_serialization(
-            &InstructionStub::FreezeLookupTable,
-            &AddressLookupTableInstruction::FreezeLookupTable,
-            4,
+        // Then the authority signature.
+        assert_eq!(
+            check_load_and_authorize(
+                &crate::id(),
+                false,
+                misaligned_data.clone(),
+                &wrong_authority
+            ),
+            Err(ProgramError::MissingRequiredSignature),
         );
-        assert_instruction_serialization(
-            &InstructionStub::ExtendLookupTable { vector_len: 4 },
-            &AddressLookupTableInstruction::ExtendLookupTable {
-                new_addresses: vec![Pubkey::new_unique(); 4],
-            },
-            12, // Check the vector length as well.
+        // Then the table data.
+        assert_eq!(
+            check_load_and_authorize(&crate::id(), true, misaligned_data, &wrong_authority),
+            Err(ProgramError::InvalidAccountData),
         );
-        assert_instruction_serialization(
-            &InstructionStub::DeactivateLookupTable,
-            &AddressLookupTableInstruction::DeactivateLookupTable,
-            4,
+        // Then whether the table is frozen.
+        assert_eq!(
+            check_load_and_authorize(&crate::id(), true, table_data(None), &wrong_authority),
+            Err(ProgramError::Immutable),
         );
-        assert_instruction_serialization(
-            &InstructionStub::CloseLookupTable,
-            &AddressLookupTableInstruction::CloseLookupTable,
-            4,
+        // And finally the authority itself.
+        assert_eq!(
+            check_load_and_authorize(
+                &crate::id(),
+                true,
+                table_data(Some(authority)),
+                &wrong_authority
+            ),
+            Err(ProgramError::IncorrectAuthority),
         );
+
+        let lookup_table_meta =
+            check_load_and_authorize(&crate::id(), true, table_data(Some(authority)), &authority)
+                .unwrap();
+        assert_eq!(lookup_table_meta.authority, Some(authority));
+    }
+
+    #[test]
+    fn test_validate_account_count() {
+        let keys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let mut lamports = [0u64; 3];
+        let mut data = [[0u8; 0]; 3];
+        let accounts: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(
+                    key,
+                    false,
+                    false,
+                    lamports,
+                    data,
+                    &system_program::ID,
+                    false,
+                    Epoch::default(),
+                )
+            })
+            .collect();
+
+        for instruction in AddressLookupTableInstruction::iter() {
+            let min_accounts = match instruction {
+                AddressLookupTableInstruction::FreezeLookupTable
+                | AddressLookupTableInstruction::ExtendLookupTable { .. }
+                | AddressLookupTableInstruction::DeactivateLookupTable
+                | AddressLookupTableInstruction::ReactivateLookupTable
+                | AddressLookupTableInstruction::ExtendLookupTableDedup { .. } => 2,
+                _ => 3,
+            };
+
+            for num_accounts in 0..min_accounts {
+                // Missing accounts are reported by `process` before any
+                // other validation.
+                assert_eq!(
+                    process(&crate::id(), &accounts[..num_accounts], &instruction.pack()),
+                    Err(ProgramError::NotEnoughAccountKeys),
+                    "{instruction:?} with {num_accounts} accounts",
+                );
+            }
+            assert_eq!(
+                validate_account_count(&instruction, &accounts[..min_accounts]),
+                Ok(()),
+                "{instruction:?} with {min_accounts} accounts",
+            );
+        }
+    }
+
+    struct SlotHashesSyscallStubs {
+        data: Vec<u8>,
+    }
+
+    impl SyscallStubs for SlotHashesSyscallStubs {
+        fn sol_get_sysvar(
+            &self,
+            _sysvar_id_addr: *const u8,
+            var_addr: *mut u8,
+            offset: u64,
+            length: u64,
+        ) -> u64 {
+            let (offset, length) = (offset as usize, length as usize);
+            let var = unsafe { std::slice::from_raw_parts_mut(var_addr, length) };
+            var.copy_from_slice(&self.data[offset..offset.saturating_add(length)]);
+            SUCCESS
+        }
+    }
+
+    fn set_slot_hashes(slot_hashes: &[SlotHash]) {
+        // Like the sysvar account, the data is always allocated at its
+        // maximum size.
+        let mut data = vec![0; SlotHashes::size_of()];
+        bincode::serialize_into(&mut data[..], &SlotHashes::new(slot_hashes)).unwrap();
+        set_syscall_stubs(Box::new(SlotHashesSyscallStubs { data }));
+    }
+
+    #[test]
+    fn test_slot_hashes_contains() {
+        // Syscall stubs are global, so all cases share this one test.
+        set_slot_hashes(&[]);
+        assert_eq!(slot_hashes_contains(&1), Ok(false));
+
+        set_slot_hashes(&[
+            (2, Hash::new_unique()),
+            (3, Hash::new_unique()),
+            (5, Hash::new_unique()),
+        ]);
+        assert_eq!(slot_hashes_contains(&2), Ok(true));
+        assert_eq!(slot_hashes_contains(&3), Ok(true));
+        assert_eq!(slot_hashes_contains(&5), Ok(true));
+        assert_eq!(slot_hashes_contains(&1), Ok(false));
+        assert_eq!(slot_hashes_contains(&4), Ok(false));
+        assert_eq!(slot_hashes_contains(&6), Ok(false));
     }
 }
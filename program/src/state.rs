@@ -1,9 +1,16 @@
 #[cfg(feature = "frozen-abi")]
-use solana_frozen_abi_macro::{AbiEnumVisitor, AbiExample};
+use solana_frozen_abi_macro::{frozen_abi, AbiEnumVisitor, AbiExample};
 use {
+    crate::error::{LookupTableValidationError, ParseLookupTableStatusError},
     serde::{Deserialize, Serialize},
-    solana_program::{clock::Slot, program_error::ProgramError, pubkey::Pubkey},
-    std::borrow::Cow,
+    solana_program::{
+        clock::Slot,
+        program_error::ProgramError,
+        pubkey::{Pubkey, PUBKEY_BYTES},
+        rent::Rent,
+        slot_hashes::MAX_ENTRIES,
+    },
+    std::{borrow::Cow, collections::HashSet, fmt, str::FromStr},
 };
 
 /// The maximum number of addresses that a lookup table can hold
@@ -12,8 +19,158 @@ pub const LOOKUP_TABLE_MAX_ADDRESSES: usize = 256;
 /// The serialized size of lookup table metadata
 pub const LOOKUP_TABLE_META_SIZE: usize = 56;
 
+// The number of addresses listed by `AddressLookupTable::summary`.
+#[cfg(feature = "cli")]
+const SUMMARY_PREVIEW_ADDRESSES: usize = 5;
+
+// The maximum bincode-serialized size of `ProgramState`: a `u32` enum
+// discriminator followed by a `LookupTableMeta` with a populated authority.
+const PROGRAM_STATE_MAX_SIZE: usize = std::mem::size_of::<u32>() // discriminator
+    + std::mem::size_of::<Slot>() // deactivation_slot
+    + std::mem::size_of::<Slot>() // last_extended_slot
+    + std::mem::size_of::<u8>() // last_extended_slot_start_index
+    + 1 // authority option tag
+    + PUBKEY_BYTES // authority
+    + std::mem::size_of::<u16>(); // _padding
+
+const _: () = assert!(PROGRAM_STATE_MAX_SIZE == LOOKUP_TABLE_META_SIZE);
+
+/// Returns the minimum lamport balance required for a lookup table account
+/// holding `num_addresses` addresses to be rent exempt.
+pub fn rent_for_addresses(rent: &Rent, num_addresses: usize) -> u64 {
+    rent.minimum_balance(
+        LOOKUP_TABLE_META_SIZE.saturating_add(num_addresses.saturating_mul(PUBKEY_BYTES)),
+    )
+}
+
+/// Returns the lamports refunded to the recipient when a lookup table
+/// account holding `table_lamports` is closed.
+///
+/// Closing a table drains its entire balance into the recipient, so the
+/// refund is always the table account's current balance. See
+/// `LookupTableClient::fetch_closable_refund` for reading it over RPC.
+pub fn closable_refund_lamports(table_lamports: u64) -> u64 {
+    table_lamports
+}
+
+/// Checks whether a lookup table can be frozen by the provided authority,
+/// replicating the checks performed by the `FreezeLookupTable` instruction.
+pub fn can_freeze(
+    table: &AddressLookupTable,
+    authority: &Pubkey,
+) -> Result<(), LookupTableValidationError> {
+    if table.is_frozen() {
+        return Err(LookupTableValidationError::LookupTableFrozen);
+    }
+    if table.meta.authority != Some(*authority) {
+        return Err(LookupTableValidationError::IncorrectAuthority);
+    }
+    if table.is_deactivated() {
+        return Err(LookupTableValidationError::LookupTableDeactivated);
+    }
+    if table.addresses.is_empty() {
+        return Err(LookupTableValidationError::LookupTableEmpty);
+    }
+    Ok(())
+}
+
+/// Activation status of a lookup table
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum LookupTableStatus {
+    Activated,
+    Deactivating { remaining_blocks: usize },
+    Deactivated,
+}
+
+impl fmt::Display for LookupTableStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LookupTableStatus::Activated => write!(f, "activated"),
+            LookupTableStatus::Deactivating { remaining_blocks } => {
+                write!(f, "deactivating({})", remaining_blocks)
+            }
+            LookupTableStatus::Deactivated => write!(f, "deactivated"),
+        }
+    }
+}
+
+impl FromStr for LookupTableStatus {
+    type Err = ParseLookupTableStatusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "activated" => Ok(LookupTableStatus::Activated),
+            "deactivated" => Ok(LookupTableStatus::Deactivated),
+            _ => s
+                .strip_prefix("deactivating(")
+                .and_then(|s| s.strip_suffix(')'))
+                .and_then(|remaining_blocks| remaining_blocks.parse().ok())
+                .map(|remaining_blocks| LookupTableStatus::Deactivating { remaining_blocks })
+                .ok_or_else(|| ParseLookupTableStatusError(s.to_string())),
+        }
+    }
+}
+
+// Shared by `LookupTableMeta::status` and `LookupTableMetaView::status`.
+fn deactivation_status(
+    deactivation_slot: Slot,
+    current_slot: Slot,
+    slot_position: Option<usize>,
+) -> LookupTableStatus {
+    if deactivation_slot == Slot::MAX {
+        LookupTableStatus::Activated
+    } else if deactivation_slot == current_slot {
+        LookupTableStatus::Deactivating {
+            remaining_blocks: MAX_ENTRIES.saturating_add(1),
+        }
+    } else if let Some(slot_position) = slot_position {
+        // Deactivation requires a cool-down period to give in-flight transactions
+        // enough time to land and to remove indeterminism caused by transactions
+        // loading addresses in the same slot when a table is closed. The
+        // cool-down period is equivalent to the amount of time it takes for
+        // a slot to be removed from the slot hash list.
+        //
+        // By using the slot hash to enforce the cool-down, there is a side effect
+        // of not allowing lookup tables to be recreated at the same derived address
+        // because tables must be created at an address derived from a recent slot.
+        LookupTableStatus::Deactivating {
+            remaining_blocks: MAX_ENTRIES.saturating_sub(slot_position),
+        }
+    } else {
+        LookupTableStatus::Deactivated
+    }
+}
+
+/// Returns the number of blocks remaining in a lookup table's deactivation
+/// cool-down period, or `None` if the table is not deactivating.
+///
+/// `recent_slots` are the slots in the `SlotHashes` sysvar, ordered from
+/// newest to oldest, as returned by RPC. This mirrors the status check
+/// performed by the program.
+pub fn remaining_cooldown_blocks(
+    deactivation_slot: Slot,
+    current_slot: Slot,
+    recent_slots: &[Slot],
+) -> Option<usize> {
+    let slot_position = recent_slots
+        .iter()
+        .position(|slot| *slot == deactivation_slot);
+    match deactivation_status(deactivation_slot, current_slot, slot_position) {
+        LookupTableStatus::Deactivating { remaining_blocks } => Some(remaining_blocks),
+        LookupTableStatus::Activated | LookupTableStatus::Deactivated => None,
+    }
+}
+
 /// Address lookup table metadata
-#[cfg_attr(feature = "frozen-abi", derive(AbiExample))]
+// The `frozen-abi` digests pin the serialized layout of lookup table
+// accounts. If a layout change is intentional, rerun the digest tests with
+// `cargo +nightly test --features frozen-abi test_abi_digest` and update the
+// digests with the values reported by the failing assertions.
+#[cfg_attr(
+    feature = "frozen-abi",
+    derive(AbiExample),
+    frozen_abi(digest = "Bdu1kMfJzaZ1uGx1AbFxhTpmWpNfXEfRybDbE7Cw9bhm")
+)]
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 pub struct LookupTableMeta {
     /// Lookup tables cannot be closed until the deactivation slot is
@@ -53,10 +210,184 @@ impl LookupTableMeta {
             ..LookupTableMeta::default()
         }
     }
+
+    /// Create lookup table metadata with the provided authority and slots,
+    /// with zeroed padding.
+    pub fn new_with_slots(
+        authority: Option<Pubkey>,
+        deactivation_slot: Slot,
+        last_extended_slot: Slot,
+        last_extended_slot_start_index: u8,
+    ) -> Self {
+        LookupTableMeta {
+            deactivation_slot,
+            last_extended_slot,
+            last_extended_slot_start_index,
+            authority,
+            _padding: 0,
+        }
+    }
+
+    /// Return the current status of the lookup table.
+    ///
+    /// `slot_position` is the position of the deactivation slot in the
+    /// `SlotHashes` sysvar, or `None` if the slot is not present.
+    pub fn status(&self, current_slot: Slot, slot_position: Option<usize>) -> LookupTableStatus {
+        deactivation_status(self.deactivation_slot, current_slot, slot_position)
+    }
+
+    /// Returns the number of blocks remaining until the lookup table can be
+    /// closed: `None` if the table is still active, `Some(0)` if it can be
+    /// closed now, or the blocks remaining in its cool-down otherwise.
+    ///
+    /// `slot_position` is the position of the deactivation slot in the
+    /// `SlotHashes` sysvar, or `None` if the slot is not present.
+    pub fn slots_until_closable(
+        &self,
+        current_slot: Slot,
+        slot_position: Option<usize>,
+    ) -> Option<usize> {
+        match self.status(current_slot, slot_position) {
+            LookupTableStatus::Activated => None,
+            LookupTableStatus::Deactivating { remaining_blocks } => Some(remaining_blocks),
+            LookupTableStatus::Deactivated => Some(0),
+        }
+    }
+
+    /// Read only the authority from a lookup table account's data, without
+    /// deserializing the rest of the metadata. Returns `None` if the table
+    /// is frozen.
+    pub fn read_authority(data: &[u8]) -> Result<Option<Pubkey>, ProgramError> {
+        LookupTableMetaView::new(data).map(|meta| meta.authority())
+    }
+
+    /// Returns `true` if the table has no authority and can never be
+    /// modified again.
+    pub fn is_frozen(&self) -> bool {
+        self.authority.is_none()
+    }
+
+    /// Returns `true` if the table has been deactivated, whether or not its
+    /// cooldown has elapsed.
+    pub fn is_deactivated(&self) -> bool {
+        self.deactivation_slot != Slot::MAX
+    }
+
+    /// Compare two metas field by field, ignoring `_padding`.
+    ///
+    /// The derived `PartialEq` is kept exact, since it backs comparisons of
+    /// serialized account state. The program always zeroes the padding, but
+    /// tables written by other tools may not, so use this to compare
+    /// metadata read from such accounts.
+    pub fn eq_ignoring_padding(&self, other: &Self) -> bool {
+        self.deactivation_slot == other.deactivation_slot
+            && self.last_extended_slot == other.last_extended_slot
+            && self.last_extended_slot_start_index == other.last_extended_slot_start_index
+            && self.authority == other.authority
+    }
+}
+
+/// A read-only view over serialized lookup table metadata.
+///
+/// Reads fields directly from their fixed offsets in the bincode layout of
+/// `ProgramState::LookupTable`, avoiding the cost of deserializing the full
+/// `LookupTableMeta`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LookupTableMetaView<'a> {
+    data: &'a [u8; LOOKUP_TABLE_META_SIZE],
+}
+
+impl<'a> LookupTableMetaView<'a> {
+    // Byte offsets of each field in the serialized metadata, following the
+    // 4-byte `ProgramState` discriminator.
+    const DEACTIVATION_SLOT_OFFSET: usize = 4;
+    const LAST_EXTENDED_SLOT_OFFSET: usize = 12;
+    const LAST_EXTENDED_SLOT_START_INDEX_OFFSET: usize = 20;
+    const AUTHORITY_OPTION_OFFSET: usize = 21;
+    const AUTHORITY_OFFSET: usize = 22;
+
+    /// Create a view over a lookup table account's data, validating the
+    /// account state discriminator and the authority option tag.
+    pub fn new(data: &'a [u8]) -> Result<Self, ProgramError> {
+        let data: &[u8; LOOKUP_TABLE_META_SIZE] = data
+            .get(0..LOOKUP_TABLE_META_SIZE)
+            .and_then(|meta_data| meta_data.try_into().ok())
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        match u32::from_le_bytes([data[0], data[1], data[2], data[3]]) {
+            0 => return Err(ProgramError::UninitializedAccount),
+            1 => {}
+            _ => return Err(ProgramError::InvalidAccountData),
+        }
+        if data[Self::AUTHORITY_OPTION_OFFSET] > 1 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self { data })
+    }
+
+    fn read_slot(&self, offset: usize) -> Slot {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&self.data[offset..offset.saturating_add(8)]);
+        Slot::from_le_bytes(bytes)
+    }
+
+    /// The slot in which the table was deactivated, or `Slot::MAX` if the
+    /// table is active.
+    pub fn deactivation_slot(&self) -> Slot {
+        self.read_slot(Self::DEACTIVATION_SLOT_OFFSET)
+    }
+
+    /// The slot that the table was last extended.
+    pub fn last_extended_slot(&self) -> Slot {
+        self.read_slot(Self::LAST_EXTENDED_SLOT_OFFSET)
+    }
+
+    /// The start index where the table was last extended from during
+    /// the `last_extended_slot`.
+    pub fn last_extended_slot_start_index(&self) -> u8 {
+        self.data[Self::LAST_EXTENDED_SLOT_START_INDEX_OFFSET]
+    }
+
+    /// Authority address which must sign for each modification, or `None`
+    /// if the table is frozen.
+    pub fn authority(&self) -> Option<Pubkey> {
+        if self.data[Self::AUTHORITY_OPTION_OFFSET] == 0 {
+            return None;
+        }
+        let mut bytes = [0u8; PUBKEY_BYTES];
+        bytes.copy_from_slice(
+            &self.data[Self::AUTHORITY_OFFSET..Self::AUTHORITY_OFFSET.saturating_add(PUBKEY_BYTES)],
+        );
+        Some(Pubkey::new_from_array(bytes))
+    }
+
+    /// Return the current status of the lookup table.
+    ///
+    /// `slot_position` is the position of the deactivation slot in the
+    /// `SlotHashes` sysvar, or `None` if the slot is not present.
+    pub fn status(&self, current_slot: Slot, slot_position: Option<usize>) -> LookupTableStatus {
+        deactivation_status(self.deactivation_slot(), current_slot, slot_position)
+    }
+
+    /// Copy the viewed fields into an owned `LookupTableMeta`.
+    pub fn to_meta(&self) -> LookupTableMeta {
+        LookupTableMeta {
+            deactivation_slot: self.deactivation_slot(),
+            last_extended_slot: self.last_extended_slot(),
+            last_extended_slot_start_index: self.last_extended_slot_start_index(),
+            authority: self.authority(),
+            ..LookupTableMeta::default()
+        }
+    }
 }
 
 /// Program account states
-#[cfg_attr(feature = "frozen-abi", derive(AbiExample, AbiEnumVisitor))]
+#[cfg_attr(
+    feature = "frozen-abi",
+    derive(AbiExample, AbiEnumVisitor),
+    frozen_abi(digest = "EXgdLKiPdGVuv6PwAUmWCWMW6zKBq3GnGNfjKwWBfyFb")
+)]
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 #[allow(clippy::large_enum_variant)]
 pub enum ProgramState {
@@ -67,6 +398,14 @@ pub enum ProgramState {
 }
 
 impl ProgramState {
+    /// Returns `true` if the account data holds an initialized lookup table.
+    /// Uninitialized, malformed, or truncated data returns `false` rather
+    /// than an error.
+    pub fn is_initialized(data: &[u8]) -> bool {
+        data.len() >= LOOKUP_TABLE_META_SIZE
+            && matches!(bincode::deserialize(data), Ok(ProgramState::LookupTable(_)))
+    }
+
     // [Core BPF]: This is a new function that was not present in the legacy
     // built-in implementation.
     /// Serialize a new lookup table into uninitialized account data.
@@ -74,7 +413,20 @@ impl ProgramState {
         data: &mut [u8],
         authority_key: &Pubkey,
     ) -> Result<(), ProgramError> {
-        let lookup_table = ProgramState::LookupTable(LookupTableMeta::new(*authority_key));
+        Self::serialize_new_lookup_table_with_authority_option(data, Some(authority_key))
+    }
+
+    /// Serialize a new lookup table into uninitialized account data, with
+    /// an optional authority. A table serialized without an authority is
+    /// frozen.
+    pub fn serialize_new_lookup_table_with_authority_option(
+        data: &mut [u8],
+        authority_key: Option<&Pubkey>,
+    ) -> Result<(), ProgramError> {
+        let lookup_table = ProgramState::LookupTable(LookupTableMeta {
+            authority: authority_key.copied(),
+            ..LookupTableMeta::default()
+        });
         // [Core BPF]: The original builtin implementation mapped `bincode`
         // serialization errors to `InstructionError::GenericError`, but this
         // error is deprecated. The error code for failed serialization has
@@ -95,7 +447,12 @@ impl ProgramState {
     }
 }
 
+/// Address lookup table metadata and addresses.
+///
+/// With the `serde-traits` feature enabled, addresses are serialized as a
+/// plain sequence and always deserialize into an owned list.
 #[cfg_attr(feature = "frozen-abi", derive(AbiExample))]
+#[cfg_attr(feature = "serde-traits", derive(Serialize, Deserialize))]
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct AddressLookupTable<'a> {
     pub meta: LookupTableMeta,
@@ -103,6 +460,28 @@ pub struct AddressLookupTable<'a> {
 }
 
 impl<'a> AddressLookupTable<'a> {
+    /// Build an address lookup table in memory from a list of addresses,
+    /// with default metadata and the provided authority.
+    pub fn from_addresses(authority: Option<Pubkey>, addresses: Vec<Pubkey>) -> Self {
+        debug_assert!(addresses.len() <= LOOKUP_TABLE_MAX_ADDRESSES);
+        Self {
+            meta: LookupTableMeta {
+                authority,
+                ..LookupTableMeta::default()
+            },
+            addresses: Cow::Owned(addresses),
+        }
+    }
+
+    /// Convert the table into one that owns its addresses, detaching it from
+    /// any borrowed account data.
+    pub fn into_owned(self) -> AddressLookupTable<'static> {
+        AddressLookupTable {
+            meta: self.meta,
+            addresses: Cow::Owned(self.addresses.into_owned()),
+        }
+    }
+
     /// Serialize an address table's updated meta data and zero
     /// any leftover bytes.
     pub fn overwrite_meta_data(
@@ -122,15 +501,24 @@ impl<'a> AddressLookupTable<'a> {
     }
 
     /// Serialize an address table including its addresses
-    pub fn serialize_for_tests(self) -> Result<Vec<u8>, ProgramError> {
-        let mut data = vec![0; LOOKUP_TABLE_META_SIZE];
-        Self::overwrite_meta_data(&mut data, self.meta)?;
+    pub fn serialize(&self) -> Result<Vec<u8>, ProgramError> {
+        let mut data = Vec::with_capacity(
+            LOOKUP_TABLE_META_SIZE
+                .saturating_add(self.addresses.len().saturating_mul(PUBKEY_BYTES)),
+        );
+        data.resize(LOOKUP_TABLE_META_SIZE, 0);
+        Self::overwrite_meta_data(&mut data, self.meta.clone())?;
         self.addresses.iter().for_each(|address| {
             data.extend_from_slice(address.as_ref());
         });
         Ok(data)
     }
 
+    /// Serialize an address table including its addresses
+    pub fn serialize_for_tests(self) -> Result<Vec<u8>, ProgramError> {
+        self.serialize()
+    }
+
     // [Core BPF]: This is a new function that was not present in the legacy
     // built-in implementation.
     /// Mutably deserialize addresses from a lookup table's data. This function
@@ -152,8 +540,31 @@ impl<'a> AddressLookupTable<'a> {
         })
     }
 
+    /// Deserialize addresses from a lookup table's data, starting from the
+    /// provided index in the list of addresses.
+    pub fn deserialize_addresses_from_index(
+        data: &[u8],
+        index: u8,
+    ) -> Result<&[Pubkey], ProgramError> {
+        let offset = LOOKUP_TABLE_META_SIZE
+            .checked_add((index as usize).saturating_mul(std::mem::size_of::<Pubkey>()))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if offset >= data.len() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        bytemuck::try_cast_slice(&data[offset..]).map_err(|_| {
+            // Should be impossible because raw address data
+            // should be aligned and sized in multiples of 32 bytes
+            ProgramError::InvalidAccountData
+        })
+    }
+
     /// Efficiently deserialize an address table without allocating
     /// for stored addresses.
+    ///
+    /// Guarantees the metadata is initialized and the address data is
+    /// sized in multiples of 32 bytes, but does not bound the number of
+    /// addresses. See [`AddressLookupTable::deserialize_checked`].
     pub fn deserialize(data: &'a [u8]) -> Result<AddressLookupTable<'a>, ProgramError> {
         let program_state: ProgramState =
             bincode::deserialize(data).map_err(|_| ProgramError::InvalidAccountData)?;
@@ -179,6 +590,318 @@ impl<'a> AddressLookupTable<'a> {
             addresses: Cow::Borrowed(addresses),
         })
     }
+
+    /// Like [`AddressLookupTable::deserialize`], but additionally guarantees
+    /// the table holds at most `LOOKUP_TABLE_MAX_ADDRESSES` addresses,
+    /// rejecting oversized account data with `InvalidAccountData`.
+    pub fn deserialize_checked(data: &'a [u8]) -> Result<AddressLookupTable<'a>, ProgramError> {
+        let lookup_table = Self::deserialize(data)?;
+        if lookup_table.addresses.len() > LOOKUP_TABLE_MAX_ADDRESSES {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(lookup_table)
+    }
+
+    /// Like [`AddressLookupTable::deserialize_checked`], but additionally
+    /// rejects metadata whose `_padding` is not zero with
+    /// `InvalidAccountData`.
+    ///
+    /// The program zeroes the padding whenever it writes the metadata, so
+    /// non-zero padding indicates data which wasn't written by the program.
+    pub fn deserialize_strict(data: &'a [u8]) -> Result<AddressLookupTable<'a>, ProgramError> {
+        let lookup_table = Self::deserialize_checked(data)?;
+        if lookup_table.meta._padding != 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(lookup_table)
+    }
+
+    /// Deserialize a zero-copy view of an address table's metadata.
+    pub fn deserialize_meta_view(data: &[u8]) -> Result<LookupTableMetaView<'_>, ProgramError> {
+        LookupTableMetaView::new(data)
+    }
+
+    /// Verify that an account holds a valid address lookup table before
+    /// deserializing it: the account must be owned by the Address Lookup
+    /// Table program, hold initialized table metadata, and have its
+    /// addresses sized in multiples of 32 bytes.
+    pub fn verify_account(owner: &Pubkey, data: &[u8]) -> Result<(), ProgramError> {
+        if *owner != crate::id() {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if data.len() < LOOKUP_TABLE_META_SIZE {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        LookupTableMetaView::new(data)?;
+        if data
+            .len()
+            .saturating_sub(LOOKUP_TABLE_META_SIZE)
+            .checked_rem(PUBKEY_BYTES)
+            != Some(0)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
+    /// Returns the number of addresses which can be looked up in the
+    /// provided slot. Addresses appended to the table during the current
+    /// slot are not usable until the next slot.
+    pub fn active_addresses_len(&self, current_slot: Slot) -> usize {
+        if current_slot > self.meta.last_extended_slot {
+            self.addresses.len()
+        } else {
+            // Guard against a malformed start index pointing past the end
+            // of the address list.
+            (self.meta.last_extended_slot_start_index as usize).min(self.addresses.len())
+        }
+    }
+
+    /// Returns an iterator over the addresses which can be looked up in the
+    /// provided slot, excluding any appended during the current slot.
+    pub fn iter_active(&self, current_slot: Slot) -> impl Iterator<Item = &Pubkey> {
+        self.addresses
+            .iter()
+            .take(self.active_addresses_len(current_slot))
+    }
+
+    /// Splits the table's addresses into those which can be looked up in the
+    /// provided slot, and those appended during that slot which will only be
+    /// usable from the next slot.
+    pub fn split_active(&self, current_slot: Slot) -> (&[Pubkey], &[Pubkey]) {
+        self.addresses
+            .split_at(self.active_addresses_len(current_slot))
+    }
+
+    /// Returns the address stored at the provided index, or
+    /// `InvalidArgument` if the index is out of range.
+    pub fn address_at(&self, index: u8) -> Result<Pubkey, ProgramError> {
+        self.addresses
+            .get(index as usize)
+            .copied()
+            .ok_or(ProgramError::InvalidArgument)
+    }
+
+    /// Like [`AddressLookupTable::address_at`], but also returns
+    /// `InvalidArgument` for addresses appended during the provided slot,
+    /// which cannot be looked up until the next slot.
+    pub fn active_address_at(&self, index: u8, current_slot: Slot) -> Result<Pubkey, ProgramError> {
+        if index as usize >= self.active_addresses_len(current_slot) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        self.address_at(index)
+    }
+
+    /// Returns the addresses appended to the table during its
+    /// `last_extended_slot`, which may span multiple extensions within that
+    /// slot. A malformed start index past the end of the address list yields
+    /// an empty slice.
+    pub fn last_extension_addresses(&self) -> &[Pubkey] {
+        let start_index =
+            (self.meta.last_extended_slot_start_index as usize).min(self.addresses.len());
+        &self.addresses[start_index..]
+    }
+
+    /// Returns the addresses stored in the lookup table, whether they are
+    /// borrowed from account data or owned.
+    ///
+    /// ```
+    /// use {
+    ///     solana_address_lookup_table_program::state::AddressLookupTable,
+    ///     solana_program::pubkey::Pubkey,
+    /// };
+    ///
+    /// let addresses = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    /// let owned_table = AddressLookupTable::from_addresses(None, addresses.clone());
+    /// let data = owned_table.serialize().unwrap();
+    /// let borrowed_table = AddressLookupTable::deserialize(&data).unwrap();
+    ///
+    /// assert_eq!(owned_table.addresses_slice(), addresses.as_slice());
+    /// assert_eq!(borrowed_table.addresses_slice(), addresses.as_slice());
+    /// ```
+    pub fn addresses_slice(&self) -> &[Pubkey] {
+        self.addresses.as_ref()
+    }
+
+    /// Returns the table's addresses as one contiguous byte slice, for
+    /// cheaply hashing or comparing a table's address set.
+    ///
+    /// `Pubkey` is a plain 32-byte array, so this reinterprets the
+    /// addresses in place without allocating, whether they are owned or
+    /// borrowed from account data.
+    pub fn addresses_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(self.addresses_slice())
+    }
+
+    /// Returns `true` if the lookup table contains the provided address.
+    pub fn contains(&self, address: &Pubkey) -> bool {
+        self.addresses.iter().any(|a| a == address)
+    }
+
+    /// Returns the addresses in `desired` which are not yet stored in the
+    /// lookup table, preserving their order and skipping duplicates.
+    ///
+    /// The result can be used to sync a table with a canonical set of
+    /// addresses:
+    ///
+    /// ```
+    /// use {
+    ///     solana_address_lookup_table_program::{
+    ///         instruction::extend_lookup_table_chunked, state::AddressLookupTable,
+    ///     },
+    ///     solana_program::{instruction::Instruction, pubkey::Pubkey},
+    /// };
+    ///
+    /// fn sync_lookup_table(
+    ///     lookup_table: &AddressLookupTable,
+    ///     lookup_table_address: Pubkey,
+    ///     authority_address: Pubkey,
+    ///     payer_address: Pubkey,
+    ///     desired: &[Pubkey],
+    /// ) -> Vec<Instruction> {
+    ///     extend_lookup_table_chunked(
+    ///         lookup_table_address,
+    ///         authority_address,
+    ///         Some(payer_address),
+    ///         lookup_table.addresses_to_add(desired),
+    ///     )
+    /// }
+    /// ```
+    pub fn addresses_to_add(&self, desired: &[Pubkey]) -> Vec<Pubkey> {
+        let mut seen: HashSet<&Pubkey> = self.addresses.iter().collect();
+        desired
+            .iter()
+            .filter(|address| seen.insert(*address))
+            .copied()
+            .collect()
+    }
+
+    /// Returns the addresses from `other` which are not yet stored in this
+    /// lookup table, truncated so that extending this table with them never
+    /// exceeds [`LOOKUP_TABLE_MAX_ADDRESSES`].
+    pub fn merge_plan(&self, other: &AddressLookupTable) -> Vec<Pubkey> {
+        let mut addresses = self.addresses_to_add(&other.addresses);
+        addresses.truncate(self.remaining_capacity());
+        addresses
+    }
+
+    /// Returns every index in the lookup table at which the provided
+    /// address is stored. Tables may contain duplicate addresses.
+    pub fn indices_of(&self, address: &Pubkey) -> Vec<u8> {
+        // Addresses past the maximum table size cannot be referenced by a
+        // `u8` index.
+        self.addresses
+            .iter()
+            .take(LOOKUP_TABLE_MAX_ADDRESSES)
+            .enumerate()
+            .filter(|(_, a)| *a == address)
+            .map(|(index, _)| index as u8)
+            .collect()
+    }
+
+    /// Returns each address stored more than once in the lookup table, in
+    /// the order in which its first repeat occurs. Each duplicated address
+    /// is reported once, regardless of how many times it repeats.
+    pub fn duplicate_addresses(&self) -> Vec<Pubkey> {
+        let mut seen = HashSet::with_capacity(self.addresses.len());
+        let mut reported = HashSet::new();
+        self.addresses
+            .iter()
+            .filter(|address| !seen.insert(*address) && reported.insert(*address))
+            .copied()
+            .collect()
+    }
+
+    /// Returns a sorted copy of the table's addresses with duplicates
+    /// removed, for rebuilding a fresh table whose addresses can be binary
+    /// searched.
+    ///
+    /// The program always preserves insertion order and cannot reorder the
+    /// addresses of an existing table, so applying the plan requires closing
+    /// this table and extending a newly created one.
+    pub fn sort_plan(&self) -> Vec<Pubkey> {
+        let mut addresses = self.addresses.to_vec();
+        addresses.sort_unstable();
+        addresses.dedup();
+        addresses
+    }
+
+    /// Returns the number of addresses that can still be appended to the
+    /// lookup table.
+    pub fn remaining_capacity(&self) -> usize {
+        LOOKUP_TABLE_MAX_ADDRESSES.saturating_sub(self.addresses.len())
+    }
+
+    /// Returns `true` if no more addresses can be appended to the lookup
+    /// table.
+    pub fn is_full(&self) -> bool {
+        self.remaining_capacity() == 0
+    }
+
+    /// Returns the authority which must sign for each modification, or
+    /// `None` if the table is frozen.
+    pub fn authority(&self) -> Option<&Pubkey> {
+        self.meta.authority.as_ref()
+    }
+
+    /// Returns `true` if the table has no authority and can never be
+    /// modified again.
+    pub fn is_frozen(&self) -> bool {
+        self.meta.is_frozen()
+    }
+
+    /// Returns `true` if the table has been deactivated, whether or not its
+    /// cooldown has elapsed.
+    pub fn is_deactivated(&self) -> bool {
+        self.meta.is_deactivated()
+    }
+
+    /// Renders a human-readable, multi-line summary of the lookup table for
+    /// CLI output. See the `Display` implementation.
+    #[cfg(feature = "cli")]
+    pub fn summary(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Multi-line summary of the table's metadata and a preview of its
+/// addresses. Only the first few addresses are listed, followed by a count
+/// of the remaining ones.
+#[cfg(feature = "cli")]
+impl fmt::Display for AddressLookupTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.meta.authority {
+            Some(authority) => writeln!(f, "Authority: {}", authority)?,
+            None => writeln!(f, "Authority: none (frozen)")?,
+        }
+        if self.is_deactivated() {
+            writeln!(f, "Deactivation slot: {}", self.meta.deactivation_slot)?;
+        } else {
+            writeln!(f, "Deactivation slot: none (active)")?;
+        }
+        writeln!(
+            f,
+            "Last extended slot: {} (start index {})",
+            self.meta.last_extended_slot, self.meta.last_extended_slot_start_index
+        )?;
+        write!(f, "Addresses: {}", self.addresses.len())?;
+        for (index, address) in self
+            .addresses
+            .iter()
+            .take(SUMMARY_PREVIEW_ADDRESSES)
+            .enumerate()
+        {
+            write!(f, "\n  {}: {}", index, address)?;
+        }
+        let hidden = self
+            .addresses
+            .len()
+            .saturating_sub(SUMMARY_PREVIEW_ADDRESSES);
+        if hidden > 0 {
+            write!(f, "\n  ... and {} more", hidden)?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -211,6 +934,7 @@ mod tests {
         let meta_size = bincode::serialized_size(&lookup_table).unwrap();
         assert!(meta_size as usize <= LOOKUP_TABLE_META_SIZE);
         assert_eq!(meta_size as usize, 56);
+        assert_eq!(meta_size as usize, PROGRAM_STATE_MAX_SIZE);
 
         let lookup_table = ProgramState::LookupTable(LookupTableMeta::default());
         let meta_size = bincode::serialized_size(&lookup_table).unwrap();
@@ -219,87 +943,835 @@ mod tests {
     }
 
     #[test]
-    fn test_overwrite_meta_data() {
-        let meta = LookupTableMeta::new_for_tests();
-        let empty_table = ProgramState::LookupTable(meta.clone());
-        let mut serialized_table_1 = bincode::serialize(&empty_table).unwrap();
-        serialized_table_1.resize(LOOKUP_TABLE_META_SIZE, 0);
-
-        let address_table = AddressLookupTable::new_for_tests(meta, 0);
-        let mut serialized_table_2 = vec![0; LOOKUP_TABLE_META_SIZE];
-        AddressLookupTable::overwrite_meta_data(&mut serialized_table_2, address_table.meta)
-            .unwrap();
-
-        assert_eq!(serialized_table_1, serialized_table_2);
+    fn test_rent_for_addresses() {
+        let rent = Rent::default();
+        for (num_addresses, expected_lamports) in [
+            (0, 1_280_640),
+            (1, 1_503_360),
+            (38, 9_744_000),
+            (256, 58_296_960),
+        ] {
+            assert_eq!(rent_for_addresses(&rent, num_addresses), expected_lamports);
+            assert_eq!(
+                rent_for_addresses(&rent, num_addresses),
+                rent.minimum_balance(LOOKUP_TABLE_META_SIZE + num_addresses * PUBKEY_BYTES),
+            );
+        }
     }
 
     #[test]
-    fn test_deserialize() {
+    fn test_can_freeze() {
+        let authority = Pubkey::new_unique();
+        let lookup_table = AddressLookupTable::new_for_tests(LookupTableMeta::new(authority), 10);
+        assert_eq!(can_freeze(&lookup_table, &authority), Ok(()));
+
+        // Immutable.
+        let mut immutable_table = lookup_table.clone();
+        immutable_table.meta.authority = None;
         assert_eq!(
-            AddressLookupTable::deserialize(&[]).err(),
-            Some(ProgramError::InvalidAccountData),
+            can_freeze(&immutable_table, &authority),
+            Err(LookupTableValidationError::LookupTableFrozen)
         );
 
+        // Deactivated.
+        let mut deactivated_table = lookup_table.clone();
+        deactivated_table.meta.deactivation_slot = 0;
         assert_eq!(
-            AddressLookupTable::deserialize(&[0u8; LOOKUP_TABLE_META_SIZE]).err(),
-            Some(ProgramError::UninitializedAccount),
+            can_freeze(&deactivated_table, &authority),
+            Err(LookupTableValidationError::LookupTableDeactivated)
         );
 
-        fn test_case(num_addresses: usize) {
-            let lookup_table_meta = LookupTableMeta::new_for_tests();
-            let address_table = AddressLookupTable::new_for_tests(lookup_table_meta, num_addresses);
-            let address_table_data =
-                AddressLookupTable::serialize_for_tests(address_table.clone()).unwrap();
-            assert_eq!(
-                AddressLookupTable::deserialize(&address_table_data).unwrap(),
-                address_table,
-            );
-        }
+        // Wrong authority.
+        assert_eq!(
+            can_freeze(&lookup_table, &Pubkey::new_unique()),
+            Err(LookupTableValidationError::IncorrectAuthority)
+        );
 
-        for case in [0, 1, 10, 255, 256] {
-            test_case(case);
-        }
+        // Empty.
+        let empty_table = AddressLookupTable::new_for_tests(LookupTableMeta::new(authority), 0);
+        assert_eq!(
+            can_freeze(&empty_table, &authority),
+            Err(LookupTableValidationError::LookupTableEmpty)
+        );
     }
 
     #[test]
-    fn test_serialize_new_lookup_table() {
-        let authority_key = Pubkey::new_unique();
-        let check_meta = LookupTableMeta::new(authority_key);
-
-        // Success proper data size.
-        let mut data = vec![0; LOOKUP_TABLE_META_SIZE];
+    fn test_lookup_table_meta_new_with_slots() {
+        let authority = Pubkey::new_unique();
+        let meta = LookupTableMeta::new_with_slots(Some(authority), 10, 20, 5);
         assert_eq!(
-            ProgramState::serialize_new_lookup_table(&mut data, &authority_key),
-            Ok(())
+            meta,
+            LookupTableMeta {
+                deactivation_slot: 10,
+                last_extended_slot: 20,
+                last_extended_slot_start_index: 5,
+                authority: Some(authority),
+                _padding: 0,
+            }
         );
-        let deserialized = AddressLookupTable::deserialize(&data).unwrap();
-        assert_eq!(deserialized.meta, check_meta);
-        assert!(deserialized.addresses.is_empty());
-
-        // Will overwrite existing data
-        let mut data = vec![7; LOOKUP_TABLE_META_SIZE];
         assert_eq!(
-            ProgramState::serialize_new_lookup_table(&mut data, &authority_key),
-            Ok(())
+            LookupTableMeta::new_with_slots(Some(authority), Slot::MAX, 0, 0),
+            LookupTableMeta::new(authority)
         );
-        let deserialized = AddressLookupTable::deserialize(&data).unwrap();
-        assert_eq!(deserialized.meta, check_meta);
-        assert!(deserialized.addresses.is_empty());
 
-        // Fail data too small.
-        let mut data = vec![0; 5];
-        assert_eq!(
-            ProgramState::serialize_new_lookup_table(&mut data, &authority_key),
-            Err(ProgramError::AccountDataTooSmall)
-        );
+        let meta_size = bincode::serialized_size(&ProgramState::LookupTable(meta.clone())).unwrap();
+        assert_eq!(meta_size as usize, LOOKUP_TABLE_META_SIZE);
+
+        let mut data = vec![0; LOOKUP_TABLE_META_SIZE];
+        AddressLookupTable::overwrite_meta_data(&mut data, meta.clone()).unwrap();
+        assert_eq!(AddressLookupTable::deserialize(&data).unwrap().meta, meta);
     }
 
     #[test]
-    fn test_deserialize_addresses_from_index_mut() {
-        let authority_key = Pubkey::new_unique();
+    fn test_lookup_table_meta_status() {
+        let mut meta = LookupTableMeta::new_for_tests();
 
-        // Alloc space for no addresses.
-        let mut data = vec![0; LOOKUP_TABLE_META_SIZE];
+        // Not deactivated.
+        assert_eq!(meta.status(0, None), LookupTableStatus::Activated);
+        assert_eq!(meta.status(10_000, None), LookupTableStatus::Activated);
+
+        for (deactivation_slot, current_slot, slot_position, expected_status) in [
+            // Deactivated in the same slot.
+            (
+                1,
+                1,
+                None,
+                LookupTableStatus::Deactivating {
+                    remaining_blocks: MAX_ENTRIES + 1,
+                },
+            ),
+            (
+                10_000,
+                10_000,
+                None,
+                LookupTableStatus::Deactivating {
+                    remaining_blocks: MAX_ENTRIES + 1,
+                },
+            ),
+            // Deactivated one slot earlier.
+            (
+                1,
+                2,
+                Some(0),
+                LookupTableStatus::Deactivating {
+                    remaining_blocks: MAX_ENTRIES,
+                },
+            ),
+            // Arbitrary number within cooldown.
+            (
+                1,
+                40,
+                Some(38),
+                LookupTableStatus::Deactivating {
+                    remaining_blocks: MAX_ENTRIES - 38,
+                },
+            ),
+            // At the very edge of cooldown.
+            (
+                1,
+                512,
+                Some(510),
+                LookupTableStatus::Deactivating {
+                    remaining_blocks: 2,
+                },
+            ),
+            (
+                512,
+                512 + 511,
+                Some(510),
+                LookupTableStatus::Deactivating {
+                    remaining_blocks: 2,
+                },
+            ),
+            (
+                1,
+                MAX_ENTRIES as Slot + 1,
+                Some(MAX_ENTRIES - 1),
+                LookupTableStatus::Deactivating {
+                    remaining_blocks: 1,
+                },
+            ),
+            // Fully deactivated.
+            (
+                1,
+                MAX_ENTRIES as Slot + 2,
+                None,
+                LookupTableStatus::Deactivated,
+            ),
+            (0, 10_000, None, LookupTableStatus::Deactivated),
+        ] {
+            meta.deactivation_slot = deactivation_slot;
+            assert_eq!(meta.status(current_slot, slot_position), expected_status);
+        }
+    }
+
+    #[test]
+    fn test_is_initialized() {
+        // Empty and truncated buffers.
+        assert!(!ProgramState::is_initialized(&[]));
+        let data = AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 0)
+            .serialize()
+            .unwrap();
+        assert!(!ProgramState::is_initialized(&data[..4]));
+        assert!(!ProgramState::is_initialized(
+            &data[..LOOKUP_TABLE_META_SIZE - 1]
+        ));
+
+        // Uninitialized.
+        assert!(!ProgramState::is_initialized(&[0; LOOKUP_TABLE_META_SIZE]));
+
+        // Unknown discriminator.
+        let mut invalid_data = data.clone();
+        invalid_data[0] = 2;
+        assert!(!ProgramState::is_initialized(&invalid_data));
+
+        // Initialized, with and without addresses.
+        assert!(ProgramState::is_initialized(&data));
+        let data = AddressLookupTable::new_for_tests(LookupTableMeta::default(), 10)
+            .serialize()
+            .unwrap();
+        assert!(ProgramState::is_initialized(&data));
+    }
+
+    #[test]
+    fn test_closable_refund_lamports() {
+        for table_lamports in [0, 1, rent_for_addresses(&Rent::default(), 10), u64::MAX] {
+            assert_eq!(closable_refund_lamports(table_lamports), table_lamports);
+        }
+    }
+
+    #[test]
+    fn test_slots_until_closable() {
+        // Position of the deactivation slot in a `SlotHashes` sysvar holding
+        // an entry for each of the `MAX_ENTRIES` slots before the current
+        // slot, newest first.
+        let slot_position = |deactivation_slot: Slot, current_slot: Slot| {
+            current_slot
+                .checked_sub(deactivation_slot)
+                .and_then(|age| age.checked_sub(1))
+                .map(|position| position as usize)
+                .filter(|position| *position < MAX_ENTRIES)
+        };
+
+        let active_meta = LookupTableMeta::default();
+        assert_eq!(active_meta.slots_until_closable(1000, None), None);
+
+        for (deactivation_slot, current_slot, expected) in [
+            (1, 1, MAX_ENTRIES + 1), // Deactivated in the same slot
+            (1, 2, MAX_ENTRIES),     // Deactivated one slot earlier
+            (1, 40, MAX_ENTRIES - 38),
+            (1, 512, MAX_ENTRIES - 510),
+            (512, 512, MAX_ENTRIES + 1),
+            (512, 512 + 1, MAX_ENTRIES),
+            (512, 512 + 19, MAX_ENTRIES - 18),
+            (512, 512 + 511, MAX_ENTRIES - 510),
+            (10_000, 10_000, MAX_ENTRIES + 1),
+            (10_000, 10_000 + 1, MAX_ENTRIES),
+            (10_000, 10_000 + 115, MAX_ENTRIES - 114),
+            (10_000, 10_000 + 511, MAX_ENTRIES - 510),
+            (10_000, 10_000 + MAX_ENTRIES as Slot, 1), // Last slot of cooldown
+            (10_000, 10_000 + MAX_ENTRIES as Slot + 1, 0), // Closable
+            (0, MAX_ENTRIES as Slot + 1, 0),
+        ] {
+            let meta = LookupTableMeta {
+                deactivation_slot,
+                ..LookupTableMeta::default()
+            };
+            assert_eq!(
+                meta.slots_until_closable(
+                    current_slot,
+                    slot_position(deactivation_slot, current_slot)
+                ),
+                Some(expected),
+                "deactivation slot {deactivation_slot}, current slot {current_slot}",
+            );
+        }
+    }
+
+    #[test]
+    fn test_remaining_cooldown_blocks() {
+        // Slot hashes for slots 999 down to 1000 - MAX_ENTRIES, newest first.
+        let current_slot = 1000;
+        let recent_slots: Vec<Slot> = (current_slot - MAX_ENTRIES as Slot..current_slot)
+            .rev()
+            .collect();
+
+        // Not deactivated.
+        assert_eq!(
+            remaining_cooldown_blocks(Slot::MAX, current_slot, &recent_slots),
+            None
+        );
+
+        // Deactivated in the same slot.
+        assert_eq!(
+            remaining_cooldown_blocks(current_slot, current_slot, &recent_slots),
+            Some(MAX_ENTRIES + 1)
+        );
+
+        // Within cooldown.
+        assert_eq!(
+            remaining_cooldown_blocks(999, current_slot, &recent_slots),
+            Some(MAX_ENTRIES)
+        );
+        assert_eq!(
+            remaining_cooldown_blocks(950, current_slot, &recent_slots),
+            Some(MAX_ENTRIES - 49)
+        );
+        assert_eq!(
+            remaining_cooldown_blocks(1000 - MAX_ENTRIES as Slot, current_slot, &recent_slots),
+            Some(1)
+        );
+
+        // Expired.
+        assert_eq!(
+            remaining_cooldown_blocks(999 - MAX_ENTRIES as Slot, current_slot, &recent_slots),
+            None
+        );
+        assert_eq!(remaining_cooldown_blocks(950, current_slot, &[]), None);
+    }
+
+    #[test]
+    fn test_overwrite_meta_data() {
+        let meta = LookupTableMeta::new_for_tests();
+        let empty_table = ProgramState::LookupTable(meta.clone());
+        let mut serialized_table_1 = bincode::serialize(&empty_table).unwrap();
+        serialized_table_1.resize(LOOKUP_TABLE_META_SIZE, 0);
+
+        let address_table = AddressLookupTable::new_for_tests(meta, 0);
+        let mut serialized_table_2 = vec![0; LOOKUP_TABLE_META_SIZE];
+        AddressLookupTable::overwrite_meta_data(&mut serialized_table_2, address_table.meta)
+            .unwrap();
+
+        assert_eq!(serialized_table_1, serialized_table_2);
+    }
+
+    #[test]
+    fn test_deserialize() {
+        assert_eq!(
+            AddressLookupTable::deserialize(&[]).err(),
+            Some(ProgramError::InvalidAccountData),
+        );
+
+        assert_eq!(
+            AddressLookupTable::deserialize(&[0u8; LOOKUP_TABLE_META_SIZE]).err(),
+            Some(ProgramError::UninitializedAccount),
+        );
+
+        fn test_case(num_addresses: usize) {
+            let lookup_table_meta = LookupTableMeta::new_for_tests();
+            let address_table = AddressLookupTable::new_for_tests(lookup_table_meta, num_addresses);
+            let address_table_data =
+                AddressLookupTable::serialize_for_tests(address_table.clone()).unwrap();
+            assert_eq!(
+                AddressLookupTable::deserialize(&address_table_data).unwrap(),
+                address_table,
+            );
+        }
+
+        for case in [0, 1, 10, 255, 256] {
+            test_case(case);
+        }
+    }
+
+    #[test]
+    fn test_last_extension_addresses() {
+        // Empty table.
+        let lookup_table = AddressLookupTable::new_for_tests(LookupTableMeta::default(), 0);
+        assert!(lookup_table.last_extension_addresses().is_empty());
+
+        // A single extension appending the last three addresses.
+        let mut lookup_table = AddressLookupTable::new_for_tests(LookupTableMeta::default(), 10);
+        lookup_table.meta.last_extended_slot = 1;
+        lookup_table.meta.last_extended_slot_start_index = 7;
+        assert_eq!(
+            lookup_table.last_extension_addresses(),
+            &lookup_table.addresses[7..],
+        );
+
+        // A second extension in the same slot keeps the original start
+        // index, so both extensions' addresses are returned.
+        let mut addresses = lookup_table.addresses.to_vec();
+        addresses.extend([Pubkey::new_unique(), Pubkey::new_unique()]);
+        lookup_table.addresses = Cow::Owned(addresses);
+        assert_eq!(lookup_table.last_extension_addresses().len(), 5);
+        assert_eq!(
+            lookup_table.last_extension_addresses(),
+            &lookup_table.addresses[7..],
+        );
+
+        // An out-of-range start index is clamped.
+        lookup_table.meta.last_extended_slot_start_index = 200;
+        assert!(lookup_table.last_extension_addresses().is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_addresses() {
+        let lookup_table = AddressLookupTable::new_for_tests(LookupTableMeta::default(), 10);
+        assert!(lookup_table.duplicate_addresses().is_empty());
+
+        // One address duplicated at two positions.
+        let [a, b, c] = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let lookup_table = AddressLookupTable {
+            meta: LookupTableMeta::default(),
+            addresses: Cow::Owned(vec![a, b, c, a]),
+        };
+        assert_eq!(lookup_table.duplicate_addresses(), vec![a]);
+
+        // Several distinct duplicates, one repeated more than twice.
+        let lookup_table = AddressLookupTable {
+            meta: LookupTableMeta::default(),
+            addresses: Cow::Owned(vec![a, b, c, b, a, b, c]),
+        };
+        assert_eq!(lookup_table.duplicate_addresses(), vec![b, a, c]);
+    }
+
+    #[test]
+    fn test_is_frozen_and_is_deactivated() {
+        // Active.
+        let meta = LookupTableMeta::new_for_tests();
+        assert!(!meta.is_frozen());
+        assert!(!meta.is_deactivated());
+
+        // Frozen.
+        let meta = LookupTableMeta {
+            authority: None,
+            ..LookupTableMeta::new_for_tests()
+        };
+        assert!(meta.is_frozen());
+        assert!(!meta.is_deactivated());
+
+        // Deactivated, whether or not the cooldown has elapsed.
+        for deactivation_slot in [0, 100, Slot::MAX - 1] {
+            let meta = LookupTableMeta {
+                deactivation_slot,
+                ..LookupTableMeta::new_for_tests()
+            };
+            assert!(!meta.is_frozen());
+            assert!(meta.is_deactivated());
+
+            let lookup_table = AddressLookupTable::new_for_tests(meta, 1);
+            assert!(!lookup_table.is_frozen());
+            assert!(lookup_table.is_deactivated());
+        }
+    }
+
+    #[test]
+    fn test_read_authority() {
+        // Owned.
+        let authority = Pubkey::new_unique();
+        let data = AddressLookupTable::new_for_tests(LookupTableMeta::new(authority), 2)
+            .serialize()
+            .unwrap();
+        assert_eq!(LookupTableMeta::read_authority(&data), Ok(Some(authority)));
+
+        // Frozen.
+        let data = AddressLookupTable::new_for_tests(LookupTableMeta::default(), 2)
+            .serialize()
+            .unwrap();
+        assert_eq!(LookupTableMeta::read_authority(&data), Ok(None));
+
+        // Uninitialized.
+        assert_eq!(
+            LookupTableMeta::read_authority(&[0; LOOKUP_TABLE_META_SIZE]),
+            Err(ProgramError::UninitializedAccount),
+        );
+    }
+
+    #[test]
+    fn test_split_active() {
+        // Empty table.
+        let lookup_table = AddressLookupTable::new_for_tests(LookupTableMeta::default(), 0);
+        assert_eq!(lookup_table.split_active(1), (&[][..], &[][..]));
+
+        let mut lookup_table = AddressLookupTable::new_for_tests(LookupTableMeta::default(), 10);
+        lookup_table.meta.last_extended_slot = 5;
+        lookup_table.meta.last_extended_slot_start_index = 7;
+
+        // Extended in the current slot.
+        let (active, pending) = lookup_table.split_active(5);
+        assert_eq!(active, &lookup_table.addresses[..7]);
+        assert_eq!(pending, &lookup_table.addresses[7..]);
+
+        // Extended in a prior slot.
+        let (active, pending) = lookup_table.split_active(6);
+        assert_eq!(active, &lookup_table.addresses[..]);
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn test_address_at() {
+        let mut lookup_table = AddressLookupTable::new_for_tests(LookupTableMeta::default(), 10);
+        lookup_table.meta.last_extended_slot = 5;
+        lookup_table.meta.last_extended_slot_start_index = 7;
+
+        // Valid indices.
+        assert_eq!(lookup_table.address_at(0), Ok(lookup_table.addresses[0]));
+        assert_eq!(lookup_table.address_at(9), Ok(lookup_table.addresses[9]));
+        assert_eq!(
+            lookup_table.active_address_at(6, 5),
+            Ok(lookup_table.addresses[6])
+        );
+
+        // Out-of-range indices.
+        for index in [10, u8::MAX] {
+            assert_eq!(
+                lookup_table.address_at(index),
+                Err(ProgramError::InvalidArgument)
+            );
+            assert_eq!(
+                lookup_table.active_address_at(index, 6),
+                Err(ProgramError::InvalidArgument)
+            );
+        }
+
+        // Addresses extended in the current slot are pending until the next.
+        assert_eq!(
+            lookup_table.active_address_at(7, 5),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            lookup_table.active_address_at(7, 6),
+            Ok(lookup_table.addresses[7])
+        );
+    }
+
+    #[test]
+    fn test_lookup_table_status_display_from_str() {
+        for (status, expected) in [
+            (LookupTableStatus::Activated, "activated"),
+            (
+                LookupTableStatus::Deactivating {
+                    remaining_blocks: 0,
+                },
+                "deactivating(0)",
+            ),
+            (
+                LookupTableStatus::Deactivating {
+                    remaining_blocks: MAX_ENTRIES + 1,
+                },
+                "deactivating(513)",
+            ),
+            (LookupTableStatus::Deactivated, "deactivated"),
+        ] {
+            assert_eq!(status.to_string(), expected);
+            assert_eq!(expected.parse::<LookupTableStatus>(), Ok(status));
+        }
+
+        for invalid in [
+            "",
+            "Activated",
+            "deactivating",
+            "deactivating()",
+            "deactivating(-1)",
+            "deactivating(1",
+        ] {
+            assert_eq!(
+                invalid.parse::<LookupTableStatus>(),
+                Err(ParseLookupTableStatusError(invalid.to_string())),
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_account() {
+        let program_id = crate::id();
+        let data = AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 10)
+            .serialize()
+            .unwrap();
+
+        assert_eq!(
+            AddressLookupTable::verify_account(&program_id, &data),
+            Ok(())
+        );
+
+        // Wrong owner.
+        assert_eq!(
+            AddressLookupTable::verify_account(&Pubkey::new_unique(), &data),
+            Err(ProgramError::InvalidAccountOwner),
+        );
+
+        // Truncated metadata.
+        assert_eq!(
+            AddressLookupTable::verify_account(&program_id, &data[..LOOKUP_TABLE_META_SIZE - 1]),
+            Err(ProgramError::AccountDataTooSmall),
+        );
+
+        // Uninitialized.
+        assert_eq!(
+            AddressLookupTable::verify_account(&program_id, &[0; LOOKUP_TABLE_META_SIZE]),
+            Err(ProgramError::UninitializedAccount),
+        );
+
+        // Misaligned addresses.
+        assert_eq!(
+            AddressLookupTable::verify_account(&program_id, &data[..data.len() - 1]),
+            Err(ProgramError::InvalidAccountData),
+        );
+    }
+
+    #[test]
+    fn test_deserialize_strict() {
+        let lookup_table = AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 3);
+        let data = lookup_table.clone().serialize_for_tests().unwrap();
+        assert_eq!(
+            AddressLookupTable::deserialize_strict(&data),
+            Ok(lookup_table.clone()),
+        );
+
+        // Non-zero padding is only rejected by the strict variant.
+        let padded_table = AddressLookupTable {
+            meta: LookupTableMeta {
+                _padding: 0xff00,
+                ..lookup_table.meta
+            },
+            addresses: lookup_table.addresses.clone(),
+        };
+        let padded_data = padded_table.clone().serialize_for_tests().unwrap();
+        assert_eq!(
+            AddressLookupTable::deserialize(&padded_data),
+            Ok(padded_table)
+        );
+        assert_eq!(
+            AddressLookupTable::deserialize_strict(&padded_data),
+            Err(ProgramError::InvalidAccountData),
+        );
+
+        // The checked variant's size bound still applies.
+        let mut oversized_data = AddressLookupTable::new_for_tests(
+            LookupTableMeta::new_for_tests(),
+            LOOKUP_TABLE_MAX_ADDRESSES,
+        )
+        .serialize_for_tests()
+        .unwrap();
+        oversized_data.extend_from_slice(Pubkey::new_unique().as_ref());
+        assert_eq!(
+            AddressLookupTable::deserialize_strict(&oversized_data),
+            Err(ProgramError::InvalidAccountData),
+        );
+    }
+
+    #[test]
+    fn test_deserialize_checked() {
+        let full_table = AddressLookupTable::new_for_tests(
+            LookupTableMeta::new_for_tests(),
+            LOOKUP_TABLE_MAX_ADDRESSES,
+        );
+        let data = full_table.clone().serialize_for_tests().unwrap();
+        assert_eq!(
+            AddressLookupTable::deserialize_checked(&data),
+            Ok(full_table),
+        );
+
+        // An over-long buffer is only rejected by the checked variant.
+        let mut oversized_data = data;
+        oversized_data.extend_from_slice(Pubkey::new_unique().as_ref());
+        assert_eq!(
+            AddressLookupTable::deserialize(&oversized_data)
+                .unwrap()
+                .addresses
+                .len(),
+            LOOKUP_TABLE_MAX_ADDRESSES + 1,
+        );
+        assert_eq!(
+            AddressLookupTable::deserialize_checked(&oversized_data),
+            Err(ProgramError::InvalidAccountData),
+        );
+
+        assert_eq!(
+            AddressLookupTable::deserialize_checked(&[0u8; LOOKUP_TABLE_META_SIZE]),
+            Err(ProgramError::UninitializedAccount),
+        );
+    }
+
+    #[test]
+    fn test_deserialize_meta_view() {
+        assert_eq!(
+            AddressLookupTable::deserialize_meta_view(&[]).err(),
+            Some(ProgramError::InvalidAccountData),
+        );
+        assert_eq!(
+            AddressLookupTable::deserialize_meta_view(&[0u8; LOOKUP_TABLE_META_SIZE]).err(),
+            Some(ProgramError::UninitializedAccount),
+        );
+
+        let mut invalid_discriminator = vec![0u8; LOOKUP_TABLE_META_SIZE];
+        invalid_discriminator[0] = 2;
+        assert_eq!(
+            AddressLookupTable::deserialize_meta_view(&invalid_discriminator).err(),
+            Some(ProgramError::InvalidAccountData),
+        );
+
+        let mut invalid_option_tag =
+            AddressLookupTable::new_for_tests(LookupTableMeta::default(), 0)
+                .serialize()
+                .unwrap();
+        invalid_option_tag[21] = 2;
+        assert!(AddressLookupTable::deserialize(&invalid_option_tag).is_err());
+        assert_eq!(
+            AddressLookupTable::deserialize_meta_view(&invalid_option_tag).err(),
+            Some(ProgramError::InvalidAccountData),
+        );
+
+        for meta in [
+            LookupTableMeta::default(),
+            LookupTableMeta::new_for_tests(),
+            LookupTableMeta::new_with_slots(Some(Pubkey::new_unique()), 123, 456, 7),
+            LookupTableMeta::new_with_slots(None, 0, Slot::MAX - 1, u8::MAX),
+        ] {
+            for num_addresses in [0, 1, 256] {
+                let data = AddressLookupTable::new_for_tests(meta.clone(), num_addresses)
+                    .serialize()
+                    .unwrap();
+                let deserialized_meta = AddressLookupTable::deserialize(&data).unwrap().meta;
+                let view = AddressLookupTable::deserialize_meta_view(&data).unwrap();
+
+                assert_eq!(
+                    view.deactivation_slot(),
+                    deserialized_meta.deactivation_slot
+                );
+                assert_eq!(
+                    view.last_extended_slot(),
+                    deserialized_meta.last_extended_slot
+                );
+                assert_eq!(
+                    view.last_extended_slot_start_index(),
+                    deserialized_meta.last_extended_slot_start_index
+                );
+                assert_eq!(view.authority(), deserialized_meta.authority);
+                assert_eq!(view.to_meta(), deserialized_meta);
+                for (current_slot, slot_position) in [(0, None), (123, None), (124, Some(0))] {
+                    assert_eq!(
+                        view.status(current_slot, slot_position),
+                        deserialized_meta.status(current_slot, slot_position)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_addresses() {
+        for (authority, num_addresses) in [
+            (None, 0),
+            (Some(Pubkey::new_unique()), 1),
+            (Some(Pubkey::new_unique()), LOOKUP_TABLE_MAX_ADDRESSES),
+        ] {
+            let mut addresses = Vec::with_capacity(num_addresses);
+            addresses.resize_with(num_addresses, Pubkey::new_unique);
+
+            let lookup_table = AddressLookupTable::from_addresses(authority, addresses.clone());
+            assert_eq!(lookup_table.meta.authority, authority);
+            assert_eq!(lookup_table.meta.deactivation_slot, Slot::MAX);
+            assert_eq!(lookup_table.addresses.as_ref(), addresses.as_slice());
+
+            let data = lookup_table.serialize().unwrap();
+            assert_eq!(
+                AddressLookupTable::deserialize(&data).unwrap(),
+                lookup_table
+            );
+        }
+    }
+
+    #[test]
+    fn test_serialize() {
+        for num_addresses in [0, 1, 256] {
+            let address_table =
+                AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), num_addresses);
+            let data = address_table.serialize().unwrap();
+            assert_eq!(
+                data.len(),
+                LOOKUP_TABLE_META_SIZE + num_addresses * PUBKEY_BYTES
+            );
+
+            // The meta, including padding, matches `overwrite_meta_data`.
+            let mut meta_data = vec![0; LOOKUP_TABLE_META_SIZE];
+            AddressLookupTable::overwrite_meta_data(&mut meta_data, address_table.meta.clone())
+                .unwrap();
+            assert_eq!(&data[..LOOKUP_TABLE_META_SIZE], &meta_data[..]);
+
+            assert_eq!(
+                AddressLookupTable::deserialize(&data).unwrap(),
+                address_table
+            );
+        }
+    }
+
+    #[test]
+    fn test_serialize_new_lookup_table() {
+        let authority_key = Pubkey::new_unique();
+        let check_meta = LookupTableMeta::new(authority_key);
+
+        // Success proper data size.
+        let mut data = vec![0; LOOKUP_TABLE_META_SIZE];
+        assert_eq!(
+            ProgramState::serialize_new_lookup_table(&mut data, &authority_key),
+            Ok(())
+        );
+        let deserialized = AddressLookupTable::deserialize(&data).unwrap();
+        assert_eq!(deserialized.meta, check_meta);
+        assert!(deserialized.addresses.is_empty());
+
+        // Will overwrite existing data
+        let mut data = vec![7; LOOKUP_TABLE_META_SIZE];
+        assert_eq!(
+            ProgramState::serialize_new_lookup_table(&mut data, &authority_key),
+            Ok(())
+        );
+        let deserialized = AddressLookupTable::deserialize(&data).unwrap();
+        assert_eq!(deserialized.meta, check_meta);
+        assert!(deserialized.addresses.is_empty());
+
+        // Fail data too small.
+        let mut data = vec![0; 5];
+        assert_eq!(
+            ProgramState::serialize_new_lookup_table(&mut data, &authority_key),
+            Err(ProgramError::AccountDataTooSmall)
+        );
+    }
+
+    #[test]
+    fn test_serialize_new_lookup_table_with_authority_option() {
+        let authority_key = Pubkey::new_unique();
+
+        for authority in [Some(&authority_key), None] {
+            let check_meta = LookupTableMeta {
+                authority: authority.copied(),
+                ..LookupTableMeta::default()
+            };
+
+            let mut data = vec![0; LOOKUP_TABLE_META_SIZE];
+            assert_eq!(
+                ProgramState::serialize_new_lookup_table_with_authority_option(
+                    &mut data, authority
+                ),
+                Ok(())
+            );
+            let deserialized = AddressLookupTable::deserialize(&data).unwrap();
+            assert_eq!(deserialized.meta, check_meta);
+            assert_eq!(deserialized.is_frozen(), authority.is_none());
+            assert!(deserialized.addresses.is_empty());
+
+            // Fail data too small.
+            let mut data = vec![0; 5];
+            assert_eq!(
+                ProgramState::serialize_new_lookup_table_with_authority_option(
+                    &mut data, authority
+                ),
+                Err(ProgramError::AccountDataTooSmall)
+            );
+        }
+    }
+
+    #[test]
+    fn test_deserialize_addresses_from_index_mut() {
+        let authority_key = Pubkey::new_unique();
+
+        // Alloc space for no addresses.
+        let mut data = vec![0; LOOKUP_TABLE_META_SIZE];
         ProgramState::serialize_new_lookup_table(&mut data, &authority_key).unwrap();
 
         // Cannot deserialize from the addresses offset if there are no
@@ -331,4 +1803,368 @@ mod tests {
         addresses[1] = pubkey2;
         assert_eq!(&addresses, &[pubkey1, pubkey2]);
     }
+
+    #[test]
+    fn test_deserialize_addresses_from_index() {
+        let lookup_table = AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 10);
+        let data = lookup_table.serialize().unwrap();
+
+        // In range.
+        assert_eq!(
+            AddressLookupTable::deserialize_addresses_from_index(&data, 0),
+            Ok(&lookup_table.addresses[..])
+        );
+        assert_eq!(
+            AddressLookupTable::deserialize_addresses_from_index(&data, 4),
+            Ok(&lookup_table.addresses[4..])
+        );
+        assert_eq!(
+            AddressLookupTable::deserialize_addresses_from_index(&data, 9),
+            Ok(&lookup_table.addresses[9..])
+        );
+
+        // Empty tail, at the end of the address list.
+        assert_eq!(
+            AddressLookupTable::deserialize_addresses_from_index(&data, 10),
+            Err(ProgramError::InvalidArgument)
+        );
+
+        // Out of range.
+        assert_eq!(
+            AddressLookupTable::deserialize_addresses_from_index(&data, 11),
+            Err(ProgramError::InvalidArgument)
+        );
+
+        // No addresses.
+        let mut data = vec![0; LOOKUP_TABLE_META_SIZE];
+        ProgramState::serialize_new_lookup_table(&mut data, &Pubkey::new_unique()).unwrap();
+        assert_eq!(
+            AddressLookupTable::deserialize_addresses_from_index(&data, 0),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_active_addresses_len() {
+        // Empty table.
+        let lookup_table = AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 0);
+        assert_eq!(lookup_table.active_addresses_len(0), 0);
+        assert_eq!(lookup_table.active_addresses_len(10), 0);
+
+        let mut lookup_table =
+            AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 10);
+        lookup_table.meta.last_extended_slot = 5;
+        lookup_table.meta.last_extended_slot_start_index = 4;
+
+        // Extended in the current slot.
+        assert_eq!(lookup_table.active_addresses_len(5), 4);
+
+        // Extended in a prior slot.
+        assert_eq!(lookup_table.active_addresses_len(6), 10);
+        assert_eq!(lookup_table.active_addresses_len(1_000), 10);
+
+        // Malformed start index.
+        lookup_table.meta.last_extended_slot_start_index = 20;
+        assert_eq!(lookup_table.active_addresses_len(5), 10);
+    }
+
+    #[test]
+    fn test_contains() {
+        let address = Pubkey::new_unique();
+
+        // Empty table.
+        let lookup_table = AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 0);
+        assert!(!lookup_table.contains(&address));
+
+        // Absent address.
+        let mut lookup_table =
+            AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 10);
+        assert!(!lookup_table.contains(&address));
+
+        // Present address.
+        lookup_table.addresses.to_mut()[5] = address;
+        assert!(lookup_table.contains(&address));
+
+        // Present address in a borrowed table.
+        let data = lookup_table.serialize_for_tests().unwrap();
+        let lookup_table = AddressLookupTable::deserialize(&data).unwrap();
+        assert!(lookup_table.contains(&address));
+    }
+
+    #[test]
+    fn test_indices_of() {
+        let address = Pubkey::new_unique();
+
+        // No matches.
+        let mut lookup_table =
+            AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 256);
+        assert!(lookup_table.indices_of(&address).is_empty());
+
+        // Single match.
+        lookup_table.addresses.to_mut()[7] = address;
+        assert_eq!(lookup_table.indices_of(&address), vec![7]);
+
+        // Duplicate entries, including the first and last index.
+        lookup_table.addresses.to_mut()[0] = address;
+        lookup_table.addresses.to_mut()[100] = address;
+        lookup_table.addresses.to_mut()[255] = address;
+        assert_eq!(lookup_table.indices_of(&address), vec![0, 7, 100, 255]);
+    }
+
+    #[test]
+    fn test_remaining_capacity() {
+        for (num_addresses, expected_capacity) in [
+            (0, LOOKUP_TABLE_MAX_ADDRESSES),
+            (1, LOOKUP_TABLE_MAX_ADDRESSES - 1),
+            (100, LOOKUP_TABLE_MAX_ADDRESSES - 100),
+            (LOOKUP_TABLE_MAX_ADDRESSES, 0),
+            // Malformed table holding more than the maximum.
+            (LOOKUP_TABLE_MAX_ADDRESSES + 1, 0),
+        ] {
+            let lookup_table =
+                AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), num_addresses);
+            assert_eq!(lookup_table.remaining_capacity(), expected_capacity);
+            assert_eq!(lookup_table.is_full(), expected_capacity == 0);
+        }
+    }
+
+    #[cfg(feature = "serde-traits")]
+    #[test]
+    fn test_serde_json_round_trip() {
+        for num_addresses in [0, 1, 256] {
+            let lookup_table =
+                AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), num_addresses);
+            let json = serde_json::to_string(&lookup_table).unwrap();
+            let deserialized: AddressLookupTable = serde_json::from_str(&json).unwrap();
+            assert_eq!(deserialized, lookup_table);
+
+            // Borrowed tables serialize identically.
+            let data = lookup_table.serialize().unwrap();
+            let borrowed_table = AddressLookupTable::deserialize(&data).unwrap();
+            assert_eq!(serde_json::to_string(&borrowed_table).unwrap(), json);
+        }
+    }
+
+    #[test]
+    fn test_iter_active() {
+        let mut lookup_table =
+            AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 10);
+        lookup_table.meta.last_extended_slot = 5;
+        lookup_table.meta.last_extended_slot_start_index = 4;
+
+        // Addresses extended in the current slot are excluded.
+        assert!(lookup_table
+            .iter_active(5)
+            .eq(lookup_table.addresses[..4].iter()));
+
+        // Addresses extended in a prior slot are included.
+        assert!(lookup_table
+            .iter_active(6)
+            .eq(lookup_table.addresses.iter()));
+
+        // No active addresses when the whole table was extended in the
+        // current slot.
+        lookup_table.meta.last_extended_slot_start_index = 0;
+        assert_eq!(lookup_table.iter_active(5).count(), 0);
+    }
+
+    #[test]
+    fn test_addresses_to_add() {
+        let lookup_table = AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 10);
+        let existing = lookup_table.addresses.to_vec();
+
+        // Fully synced table.
+        assert!(lookup_table.addresses_to_add(&existing).is_empty());
+        assert!(lookup_table.addresses_to_add(&[]).is_empty());
+
+        // Partial overlap preserves the order of the missing addresses.
+        let missing = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let desired = [existing[3], missing[0], existing[0], missing[1]];
+        assert_eq!(lookup_table.addresses_to_add(&desired), missing.to_vec());
+
+        // Duplicate desired entries are only added once.
+        let desired = [missing[1], missing[0], missing[1], existing[5], missing[0]];
+        assert_eq!(
+            lookup_table.addresses_to_add(&desired),
+            vec![missing[1], missing[0]]
+        );
+    }
+
+    #[test]
+    fn test_merge_plan() {
+        let lookup_table = AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 10);
+
+        // Disjoint tables merge completely.
+        let other = AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 5);
+        assert_eq!(lookup_table.merge_plan(&other), other.addresses.to_vec());
+
+        // Overlapping addresses are skipped.
+        let missing = Pubkey::new_unique();
+        let other = AddressLookupTable::from_addresses(
+            None,
+            vec![
+                lookup_table.addresses[2],
+                missing,
+                lookup_table.addresses[7],
+            ],
+        );
+        assert_eq!(lookup_table.merge_plan(&other), vec![missing]);
+
+        // Merges past the maximum table size are truncated.
+        let lookup_table = AddressLookupTable::new_for_tests(
+            LookupTableMeta::new_for_tests(),
+            LOOKUP_TABLE_MAX_ADDRESSES - 3,
+        );
+        let other = AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 5);
+        assert_eq!(
+            lookup_table.merge_plan(&other),
+            other.addresses[..3].to_vec()
+        );
+
+        // Nothing can be merged into a full table.
+        let lookup_table = AddressLookupTable::new_for_tests(
+            LookupTableMeta::new_for_tests(),
+            LOOKUP_TABLE_MAX_ADDRESSES,
+        );
+        assert!(lookup_table.merge_plan(&other).is_empty());
+    }
+
+    #[test]
+    fn test_addresses_bytes() {
+        for num_addresses in [0, 1, LOOKUP_TABLE_MAX_ADDRESSES] {
+            let owned_table =
+                AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), num_addresses);
+            let bytes = owned_table.addresses_bytes();
+            assert_eq!(bytes.len(), PUBKEY_BYTES * owned_table.addresses.len());
+            assert_eq!(
+                bytes,
+                owned_table
+                    .addresses
+                    .iter()
+                    .flat_map(|address| address.to_bytes())
+                    .collect::<Vec<u8>>(),
+            );
+
+            // Borrowed addresses are the address region of the account data.
+            let data = owned_table.clone().serialize_for_tests().unwrap();
+            let borrowed_table = AddressLookupTable::deserialize(&data).unwrap();
+            assert_eq!(
+                borrowed_table.addresses_bytes(),
+                &data[LOOKUP_TABLE_META_SIZE..]
+            );
+        }
+    }
+
+    #[test]
+    fn test_addresses_slice() {
+        let owned_table = AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 10);
+        assert!(matches!(owned_table.addresses, Cow::Owned(_)));
+        assert_eq!(owned_table.addresses_slice(), &owned_table.addresses[..]);
+
+        let data = owned_table.clone().serialize_for_tests().unwrap();
+        let borrowed_table = AddressLookupTable::deserialize(&data).unwrap();
+        assert!(matches!(borrowed_table.addresses, Cow::Borrowed(_)));
+        assert_eq!(
+            borrowed_table.addresses_slice(),
+            owned_table.addresses_slice()
+        );
+
+        // The borrowed slice points directly into the account data.
+        assert_eq!(
+            borrowed_table.addresses_slice().as_ptr() as *const u8,
+            data[LOOKUP_TABLE_META_SIZE..].as_ptr(),
+        );
+    }
+
+    #[test]
+    fn test_into_owned() {
+        let lookup_table = AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 10);
+
+        let data = lookup_table.clone().serialize_for_tests().unwrap();
+        let owned_table = AddressLookupTable::deserialize(&data).unwrap().into_owned();
+        drop(data);
+
+        assert!(matches!(owned_table.addresses, Cow::Owned(_)));
+        assert_eq!(owned_table, lookup_table);
+    }
+
+    #[test]
+    fn test_authority() {
+        let authority = Pubkey::new_unique();
+        let lookup_table = AddressLookupTable::from_addresses(Some(authority), vec![]);
+        assert_eq!(lookup_table.authority(), Some(&authority));
+
+        let frozen_table = AddressLookupTable::from_addresses(None, vec![Pubkey::new_unique()]);
+        assert_eq!(frozen_table.authority(), None);
+    }
+
+    #[test]
+    fn test_sort_plan() {
+        let mut lookup_table =
+            AddressLookupTable::new_for_tests(LookupTableMeta::new_for_tests(), 10);
+        let mut addresses = lookup_table.addresses.to_vec();
+        addresses.extend([addresses[3], addresses[0], addresses[3]]);
+        lookup_table.addresses = Cow::Owned(addresses);
+
+        let sorted = lookup_table.sort_plan();
+        assert_eq!(sorted.len(), 10);
+        assert!(sorted.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(lookup_table.addresses.iter().all(|a| sorted.contains(a)));
+
+        assert!(AddressLookupTable::from_addresses(None, vec![])
+            .sort_plan()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_eq_ignoring_padding() {
+        let meta = LookupTableMeta::new_with_slots(Some(Pubkey::new_unique()), 10, 5, 3);
+        let padded_meta = LookupTableMeta {
+            _padding: 0xffff,
+            ..meta.clone()
+        };
+        assert_ne!(meta, padded_meta);
+        assert!(meta.eq_ignoring_padding(&padded_meta));
+        assert!(padded_meta.eq_ignoring_padding(&meta));
+
+        let other_meta = LookupTableMeta {
+            last_extended_slot_start_index: 4,
+            ..padded_meta.clone()
+        };
+        assert!(!meta.eq_ignoring_padding(&other_meta));
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn test_summary() {
+        let authority = Pubkey::new_unique();
+        let addresses: Vec<Pubkey> = (0..7).map(|_| Pubkey::new_unique()).collect();
+        let mut lookup_table = AddressLookupTable::from_addresses(Some(authority), addresses);
+        lookup_table.meta.last_extended_slot = 42;
+        lookup_table.meta.last_extended_slot_start_index = 3;
+
+        let summary = lookup_table.summary();
+        assert!(summary.contains(&format!("Authority: {authority}")));
+        assert!(summary.contains("Deactivation slot: none (active)"));
+        assert!(summary.contains("Last extended slot: 42 (start index 3)"));
+        assert!(summary.contains("Addresses: 7"));
+        for (index, address) in lookup_table.addresses.iter().enumerate() {
+            assert_eq!(
+                summary.contains(&format!("{index}: {address}")),
+                index < SUMMARY_PREVIEW_ADDRESSES
+            );
+        }
+        assert!(summary.ends_with("... and 2 more"));
+
+        lookup_table.meta.authority = None;
+        lookup_table.meta.deactivation_slot = 100;
+        lookup_table.addresses = Cow::Owned(vec![]);
+        assert_eq!(
+            lookup_table.summary(),
+            "Authority: none (frozen)\n\
+             Deactivation slot: 100\n\
+             Last extended slot: 42 (start index 3)\n\
+             Addresses: 0",
+        );
+    }
 }
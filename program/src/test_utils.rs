@@ -0,0 +1,66 @@
+//! Helpers for constructing address lookup table accounts in tests.
+//!
+//! Enabled with the `test-utils` feature. Not intended for on-chain use.
+
+use {
+    crate::state::{AddressLookupTable, LookupTableMeta},
+    solana_program::{clock::Slot, pubkey::Pubkey, rent::Rent},
+    solana_sdk::account::AccountSharedData,
+    std::borrow::Cow,
+};
+
+/// Create an address lookup table holding `num_addresses` unique addresses,
+/// with the provided authority and deactivation slot.
+pub fn new_address_lookup_table(
+    authority: Option<Pubkey>,
+    num_addresses: usize,
+    deactivation_slot: Slot,
+) -> AddressLookupTable<'static> {
+    let mut addresses = Vec::with_capacity(num_addresses);
+    addresses.resize_with(num_addresses, Pubkey::new_unique);
+    AddressLookupTable {
+        meta: LookupTableMeta {
+            authority,
+            deactivation_slot,
+            ..LookupTableMeta::default()
+        },
+        addresses: Cow::Owned(addresses),
+    }
+}
+
+/// Create a rent-exempt account owned by the Address Lookup Table program
+/// holding the serialized lookup table.
+pub fn lookup_table_account(
+    address_lookup_table: AddressLookupTable<'static>,
+) -> AccountSharedData {
+    let data = address_lookup_table.serialize_for_tests().unwrap();
+    let rent_exempt_balance = Rent::default().minimum_balance(data.len());
+    let mut account = AccountSharedData::new(rent_exempt_balance, data.len(), &crate::id());
+    account.set_data_from_slice(&data);
+    account
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::account::ReadableAccount};
+
+    #[test]
+    fn test_lookup_table_account() {
+        let authority = Pubkey::new_unique();
+        let lookup_table = new_address_lookup_table(Some(authority), 10, 5);
+        assert_eq!(lookup_table.addresses.len(), 10);
+        assert_eq!(lookup_table.meta.authority, Some(authority));
+        assert_eq!(lookup_table.meta.deactivation_slot, 5);
+
+        let account = lookup_table_account(lookup_table.clone());
+        assert_eq!(account.owner(), &crate::id());
+        assert_eq!(
+            account.lamports(),
+            Rent::default().minimum_balance(account.data().len()),
+        );
+        assert_eq!(
+            AddressLookupTable::deserialize(account.data()).unwrap(),
+            lookup_table,
+        );
+    }
+}
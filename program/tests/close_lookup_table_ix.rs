@@ -5,10 +5,16 @@ mod common;
 use {
     common::{lookup_table_account, new_address_lookup_table, setup},
     mollusk_svm::result::Check,
-    solana_address_lookup_table_program::instruction::close_lookup_table,
+    solana_address_lookup_table_program::{
+        error::AddressLookupTableError,
+        instruction::{close_lookup_table, close_lookup_table_and_reassign},
+    },
     solana_sdk::{
-        account::AccountSharedData, program_error::ProgramError, pubkey::Pubkey,
+        account::{AccountSharedData, ReadableAccount},
+        program_error::ProgramError,
+        pubkey::Pubkey,
         slot_hashes::MAX_ENTRIES,
+        system_program,
     },
 };
 
@@ -49,6 +55,161 @@ fn test_close_lookup_table() {
     );
 }
 
+#[test]
+fn test_close_lookup_table_and_reassign() {
+    // The opt-in close path also hands the account back to the System
+    // program, leaving the canonical "closed" account shape.
+    let mut mollusk = setup();
+    mollusk.warp_to_slot(MAX_ENTRIES as u64 + 1);
+
+    let recipient = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let initialized_table = {
+        let mut table = new_address_lookup_table(Some(authority), 10);
+        table.meta.deactivation_slot = 0;
+        table
+    };
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+    let lookup_table_lamports = lookup_table_account.lamports();
+
+    mollusk.process_and_validate_instruction(
+        &close_lookup_table_and_reassign(lookup_table_address, authority, recipient),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (recipient, AccountSharedData::default()),
+        ],
+        &[
+            Check::success(),
+            Check::account(&lookup_table_address)
+                .data(&[])
+                .lamports(0)
+                .owner(&system_program::id())
+                .build(),
+            Check::account(&recipient)
+                .lamports(lookup_table_lamports)
+                .build(),
+        ],
+    );
+}
+
+#[test]
+fn test_close_lookup_table_and_reassign_not_deactivated() {
+    // The reassigning close path enforces the same deactivation rules.
+    let mollusk = setup();
+
+    let recipient = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let initialized_table = new_address_lookup_table(Some(authority), 0);
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    mollusk.process_and_validate_instruction(
+        &close_lookup_table_and_reassign(lookup_table_address, authority, recipient),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (recipient, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::InvalidArgument)],
+    );
+}
+
+#[test]
+fn test_close_lookup_table_to_authority() {
+    // The authority may also receive the reclaimed lamports.
+    let mut mollusk = setup();
+    mollusk.warp_to_slot(MAX_ENTRIES as u64 + 1);
+
+    let authority = Pubkey::new_unique();
+    let authority_lamports = 1_000_000;
+    let initialized_table = {
+        let mut table = new_address_lookup_table(Some(authority), 10);
+        table.meta.deactivation_slot = 0;
+        table
+    };
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+    let lookup_table_lamports = lookup_table_account.lamports();
+
+    mollusk.process_and_validate_instruction(
+        &close_lookup_table(lookup_table_address, authority, authority),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (
+                authority,
+                AccountSharedData::new(authority_lamports, 0, &Pubkey::default()),
+            ),
+        ],
+        &[
+            Check::success(),
+            Check::account(&lookup_table_address)
+                .data(&[])
+                .lamports(0)
+                .build(),
+            Check::account(&authority)
+                .lamports(authority_lamports + lookup_table_lamports)
+                .build(),
+        ],
+    );
+}
+
+#[test]
+fn test_close_already_closed_lookup_table() {
+    let mollusk = setup();
+
+    let recipient = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+
+    // A closed table has no data or lamports, but is still owned by the
+    // program until the end of the transaction.
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account =
+        AccountSharedData::new(0, 0, &solana_address_lookup_table_program::id());
+
+    mollusk.process_and_validate_instruction(
+        &close_lookup_table(lookup_table_address, authority, recipient),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (recipient, AccountSharedData::default()),
+        ],
+        &[Check::err(
+            AddressLookupTableError::LookupTableAlreadyClosed.into(),
+        )],
+    );
+}
+
+#[test]
+fn test_close_already_closed_lookup_table_without_signing() {
+    let mollusk = setup();
+
+    let recipient = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account =
+        AccountSharedData::new(0, 0, &solana_address_lookup_table_program::id());
+
+    let mut instruction = close_lookup_table(lookup_table_address, authority, recipient);
+    instruction.accounts[1].is_signer = false;
+
+    // The signer check runs before the closed table is detected.
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (recipient, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::MissingRequiredSignature)],
+    );
+}
+
 #[test]
 fn test_close_lookup_table_not_deactivated() {
     // Try to close a lookup table that hasn't first been deactivated.
@@ -160,6 +321,73 @@ fn test_close_lookup_table_deactivated() {
     }
 }
 
+#[test]
+fn test_close_lookup_table_with_misaligned_addresses() {
+    // Address data which is not a multiple of 32 bytes is rejected, just as
+    // it is by `AddressLookupTable::deserialize`.
+    let mut mollusk = setup();
+    mollusk.warp_to_slot(MAX_ENTRIES as u64 + 1);
+
+    let recipient = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let initialized_table = {
+        let mut table = new_address_lookup_table(Some(authority), 1);
+        table.meta.deactivation_slot = 0;
+        table
+    };
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = {
+        let mut account = lookup_table_account(initialized_table);
+        let mut data = account.data().to_vec();
+        data.pop();
+        account.set_data_from_slice(&data);
+        account
+    };
+
+    mollusk.process_and_validate_instruction(
+        &close_lookup_table(lookup_table_address, authority, recipient),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (recipient, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::InvalidAccountData)],
+    );
+}
+
+#[test]
+fn test_close_lookup_table_recipient_lamports_overflow() {
+    let mut mollusk = setup();
+    mollusk.warp_to_slot(MAX_ENTRIES as u64 + 1);
+
+    let recipient = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let initialized_table = {
+        let mut table = new_address_lookup_table(Some(authority), 0);
+        table.meta.deactivation_slot = 0;
+        table
+    };
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    mollusk.process_and_validate_instruction(
+        &close_lookup_table(lookup_table_address, authority, recipient),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (
+                recipient,
+                AccountSharedData::new(u64::MAX - 1, 0, &Pubkey::default()),
+            ),
+        ],
+        &[Check::err(
+            AddressLookupTableError::CloseLamportsOverflow.into(),
+        )],
+    );
+}
+
 #[test]
 fn test_close_immutable_lookup_table() {
     let mollusk = setup();
@@ -182,6 +410,28 @@ fn test_close_immutable_lookup_table() {
     );
 }
 
+#[test]
+fn test_close_immutable_lookup_table_to_itself() {
+    let mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+    let initialized_table = new_address_lookup_table(None, 0);
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    // The recipient check runs before the table's metadata is read, so it
+    // takes precedence over the frozen table error.
+    mollusk.process_and_validate_instruction(
+        &close_lookup_table(lookup_table_address, authority, lookup_table_address),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::InvalidArgument)],
+    );
+}
+
 #[test]
 fn test_close_lookup_table_with_wrong_authority() {
     let mollusk = setup();
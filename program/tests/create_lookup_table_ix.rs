@@ -6,7 +6,10 @@ use {
     common::setup,
     mollusk_svm::{program::keyed_account_for_system_program, result::Check},
     solana_address_lookup_table_program::{
-        instruction::create_lookup_table,
+        instruction::{
+            create_lookup_table, create_lookup_table_idempotent,
+            create_lookup_table_idempotent_with_bump,
+        },
         state::{AddressLookupTable, LOOKUP_TABLE_META_SIZE},
     },
     solana_sdk::{
@@ -84,6 +87,62 @@ fn test_create_lookup_table_idempotent() {
     );
 }
 
+#[test]
+fn test_create_lookup_table_idempotent_builder_replay() {
+    let mut mollusk = setup();
+
+    let test_recent_slot = 123;
+    mollusk.warp_to_slot(test_recent_slot + 1);
+
+    let payer = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let (create_lookup_table_ix, lookup_table_address) =
+        create_lookup_table_idempotent(authority, payer, test_recent_slot);
+
+    let (_, derived_address, _) =
+        create_lookup_table_idempotent_with_bump(authority, payer, test_recent_slot);
+    assert_eq!(lookup_table_address, derived_address);
+
+    let payer_account = AccountSharedData::new(100_000_000, 0, &system_program::id());
+
+    let result = mollusk.process_and_validate_instruction(
+        &create_lookup_table_ix,
+        &[
+            (lookup_table_address, AccountSharedData::default()),
+            (authority, AccountSharedData::default()),
+            (payer, payer_account),
+            keyed_account_for_system_program(),
+        ],
+        &[Check::success()],
+    );
+
+    let lookup_table_account = result.get_account(&lookup_table_address).unwrap();
+    let payer_lamports = result.get_account(&payer).unwrap().lamports();
+
+    // Replaying the same instruction succeeds and leaves both the table and
+    // the payer untouched.
+    mollusk.process_and_validate_instruction(
+        &create_lookup_table_ix,
+        &[
+            (lookup_table_address, lookup_table_account.clone()),
+            (authority, AccountSharedData::default()),
+            (
+                payer,
+                AccountSharedData::new(payer_lamports, 0, &system_program::id()),
+            ),
+            keyed_account_for_system_program(),
+        ],
+        &[
+            Check::success(),
+            Check::account(&lookup_table_address)
+                .data(lookup_table_account.data())
+                .lamports(lookup_table_account.lamports())
+                .build(),
+            Check::account(&payer).lamports(payer_lamports).build(),
+        ],
+    );
+}
+
 #[test]
 fn test_create_lookup_table_use_payer_as_authority() {
     let mut mollusk = setup();
@@ -164,3 +223,34 @@ fn test_create_lookup_table_pda_mismatch() {
         &[Check::err(ProgramError::InvalidArgument)],
     );
 }
+
+#[test]
+fn test_create_lookup_table_wrong_system_program() {
+    let mut mollusk = setup();
+
+    let test_recent_slot = 123;
+
+    // [Core BPF]: Warping to slot, which will update `SlotHashes`.
+    mollusk.warp_to_slot(test_recent_slot + 1);
+
+    let payer = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let wrong_system_program = Pubkey::new_unique();
+    let (mut create_lookup_table_ix, lookup_table_address) =
+        create_lookup_table(authority, payer, test_recent_slot);
+    create_lookup_table_ix.accounts[3].pubkey = wrong_system_program;
+
+    mollusk.process_and_validate_instruction(
+        &create_lookup_table_ix,
+        &[
+            (lookup_table_address, AccountSharedData::default()),
+            (authority, AccountSharedData::default()),
+            (
+                payer,
+                AccountSharedData::new(100_000_000, 0, &system_program::id()),
+            ),
+            (wrong_system_program, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::IncorrectProgramId)],
+    );
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::{lookup_table_account, new_address_lookup_table, setup},
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check},
+    solana_address_lookup_table_program::{
+        error::AddressLookupTableError,
+        instruction::extend_lookup_table_dedup,
+        state::{rent_for_addresses, AddressLookupTable},
+    },
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+// Extends a table holding `num_existing_addresses` addresses. The
+// `test_case` closure receives the existing addresses and returns the new
+// addresses to provide, along with the addresses expected to be appended.
+fn run_extend_dedup(
+    num_existing_addresses: usize,
+    test_case: impl FnOnce(&[Pubkey]) -> (Vec<Pubkey>, Vec<Pubkey>),
+) {
+    let mut mollusk = setup();
+    mollusk.warp_to_slot(1);
+
+    let payer = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let initialized_table = new_address_lookup_table(Some(authority), num_existing_addresses);
+    let existing_addresses = initialized_table.addresses.to_vec();
+    let (new_addresses, expected_appended) = test_case(&existing_addresses);
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    let result = mollusk.process_and_validate_instruction(
+        &extend_lookup_table_dedup(lookup_table_address, authority, Some(payer), new_addresses),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (
+                payer,
+                AccountSharedData::new(100_000_000, 0, &system_program::id()),
+            ),
+            keyed_account_for_system_program(),
+        ],
+        &[Check::success()],
+    );
+
+    let lookup_table_account = result.get_account(&lookup_table_address).unwrap();
+    let lookup_table = AddressLookupTable::deserialize(lookup_table_account.data()).unwrap();
+
+    let expected_addresses = [existing_addresses.clone(), expected_appended].concat();
+    assert_eq!(lookup_table.addresses.to_vec(), expected_addresses);
+    assert_eq!(
+        lookup_table_account.lamports(),
+        rent_for_addresses(&mollusk.sysvars.rent, expected_addresses.len()),
+    );
+    assert_eq!(
+        lookup_table.meta.last_extended_slot_start_index as usize,
+        existing_addresses.len()
+    );
+}
+
+#[test]
+fn test_extend_lookup_table_dedup_all_new() {
+    let new_addresses = vec![
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    run_extend_dedup(5, |_| (new_addresses.clone(), new_addresses.clone()));
+}
+
+#[test]
+fn test_extend_lookup_table_dedup_partial_overlap() {
+    let missing = [Pubkey::new_unique(), Pubkey::new_unique()];
+    run_extend_dedup(5, |existing| {
+        (
+            vec![existing[1], missing[0], existing[4], missing[1], missing[0]],
+            missing.to_vec(),
+        )
+    });
+}
+
+#[test]
+fn test_extend_lookup_table_dedup_all_duplicates() {
+    let mollusk = setup();
+
+    let payer = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let initialized_table = new_address_lookup_table(Some(authority), 5);
+    let new_addresses = vec![
+        initialized_table.addresses[0],
+        initialized_table.addresses[3],
+        initialized_table.addresses[0],
+    ];
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    mollusk.process_and_validate_instruction(
+        &extend_lookup_table_dedup(lookup_table_address, authority, Some(payer), new_addresses),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (
+                payer,
+                AccountSharedData::new(100_000_000, 0, &system_program::id()),
+            ),
+            keyed_account_for_system_program(),
+        ],
+        &[Check::err(AddressLookupTableError::NoNewAddresses.into())],
+    );
+}
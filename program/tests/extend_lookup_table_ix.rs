@@ -11,8 +11,13 @@ use {
     },
     solana_address_lookup_table_program::{
         error::AddressLookupTableError,
-        instruction::extend_lookup_table,
-        state::{AddressLookupTable, LookupTableMeta},
+        instruction::{
+            extend_lookup_table, extend_lookup_table_prepaid, MAX_EXTEND_ADDRESSES_PER_IX,
+        },
+        state::{
+            rent_for_addresses, AddressLookupTable, LookupTableMeta, LOOKUP_TABLE_MAX_ADDRESSES,
+            LOOKUP_TABLE_META_SIZE,
+        },
     },
     solana_sdk::{
         account::{AccountSharedData, ReadableAccount, WritableAccount},
@@ -72,15 +77,27 @@ fn test_extend_lookup_table() {
             (0, 1, Ok(())),
             (0, 10, Ok(())),
             (0, 38, Ok(())), // Max new addresses allowed by `limited_deserialize`
-            (0, 39, Err(ProgramError::InvalidInstructionData)),
+            (
+                0,
+                39,
+                Err(AddressLookupTableError::TooManyNewAddresses.into()),
+            ),
             (1, 1, Ok(())),
             (1, 10, Ok(())),
             (218, 38, Ok(())), // 38 less than maximum, 38 brings it to the maximum
-            (219, 38, Err(ProgramError::InvalidInstructionData)),
+            (
+                219,
+                38,
+                Err(AddressLookupTableError::ExtendExceedsCapacity.into()),
+            ),
             (246, 10, Ok(())),
             (255, 1, Ok(())), // One less than maximum, 1 brings it to the maximum
-            (255, 2, Err(ProgramError::InvalidInstructionData)),
-            (256, 1, Err(ProgramError::InvalidArgument)),
+            (
+                255,
+                2,
+                Err(AddressLookupTableError::ExtendExceedsCapacity.into()),
+            ),
+            (256, 1, Err(AddressLookupTableError::LookupTableFull.into())),
         ] {
             let mut lookup_table =
                 new_address_lookup_table(Some(authority), num_existing_addresses);
@@ -301,7 +318,9 @@ fn test_extend_lookup_table_without_payer() {
             (lookup_table_address, lookup_table_account),
             (authority, AccountSharedData::default()),
         ],
-        &[Check::err(ProgramError::NotEnoughAccountKeys)],
+        &[Check::err(
+            AddressLookupTableError::PayerRequiredForExtend.into(),
+        )],
     );
 }
 
@@ -357,6 +376,219 @@ fn test_extend_prepaid_lookup_table_without_payer() {
     );
 }
 
+#[test]
+fn test_extend_lookup_table_prepaid() {
+    let mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+    let lookup_table_address = Pubkey::new_unique();
+
+    let (lookup_table_account, expected_state) = {
+        // initialize lookup table
+        let empty_lookup_table = new_address_lookup_table(Some(authority), 0);
+        let mut lookup_table_account = lookup_table_account(empty_lookup_table);
+
+        // calculate required rent exempt balance for adding one address
+        let mut temp_lookup_table = new_address_lookup_table(Some(authority), 1);
+        let data = temp_lookup_table.clone().serialize_for_tests().unwrap();
+        let rent_exempt_balance = mollusk.sysvars.rent.minimum_balance(data.len());
+
+        // prepay for one address
+        lookup_table_account.set_lamports(rent_exempt_balance);
+
+        // test will extend table in the current bank's slot
+        temp_lookup_table.meta.last_extended_slot = mollusk.sysvars.clock.slot;
+
+        (
+            lookup_table_account,
+            ExpectedTableAccount {
+                lamports: rent_exempt_balance,
+                data_len: data.len(),
+                state: temp_lookup_table,
+            },
+        )
+    };
+
+    let accounts = vec![
+        (lookup_table_address, lookup_table_account),
+        (authority, AccountSharedData::default()),
+    ];
+
+    // Extending with more addresses than were prepaid requires a payer.
+    let new_addresses = expected_state.state.addresses.to_vec();
+    run_test_case(
+        &mollusk,
+        TestCase {
+            lookup_table_address,
+            instruction: extend_lookup_table_prepaid(
+                lookup_table_address,
+                authority,
+                [new_addresses.clone(), vec![Pubkey::new_unique()]].concat(),
+            ),
+            accounts: accounts.clone(),
+            expected_result: Err(AddressLookupTableError::PayerRequiredForExtend.into()),
+        },
+    );
+
+    run_test_case(
+        &mollusk,
+        TestCase {
+            lookup_table_address,
+            instruction: extend_lookup_table_prepaid(
+                lookup_table_address,
+                authority,
+                new_addresses,
+            ),
+            accounts,
+            expected_result: Ok(expected_state),
+        },
+    );
+}
+
+#[test]
+fn test_extend_partially_prepaid_lookup_table() {
+    let mut mollusk = setup();
+    mollusk.warp_to_slot(1);
+
+    let payer = Pubkey::new_unique();
+    let payer_lamports = 100_000_000;
+    let authority = Pubkey::new_unique();
+    let rent = mollusk.sysvars.rent.clone();
+
+    // Prepay for one of the three new addresses.
+    let prepaid_lamports = rent_for_addresses(&rent, 1);
+    let required_lamports = rent_for_addresses(&rent, 3);
+
+    let lookup_table_address = Pubkey::new_unique();
+    let mut lookup_table_account =
+        lookup_table_account(new_address_lookup_table(Some(authority), 0));
+    lookup_table_account.set_lamports(prepaid_lamports);
+
+    let new_addresses = vec![
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &extend_lookup_table(lookup_table_address, authority, Some(payer), new_addresses),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (
+                payer,
+                AccountSharedData::new(payer_lamports, 0, &system_program::id()),
+            ),
+            keyed_account_for_system_program(),
+        ],
+        &[
+            Check::success(),
+            Check::account(&lookup_table_address)
+                .lamports(required_lamports)
+                .build(),
+            // The payer is only charged the shortfall.
+            Check::account(&payer)
+                .lamports(payer_lamports - (required_lamports - prepaid_lamports))
+                .build(),
+        ],
+    );
+}
+
+#[test]
+fn test_extend_lookup_table_to_max_size_rent() {
+    // Extending a table up to the maximum size must charge the payer the
+    // exact shortfall, whether the table is underfunded by a single lamport
+    // or by the whole new allocation, and nothing when it is overfunded.
+    let mut mollusk = setup();
+    mollusk.warp_to_slot(1);
+
+    let payer = Pubkey::new_unique();
+    let payer_lamports = 100_000_000;
+    let authority = Pubkey::new_unique();
+    let rent = mollusk.sysvars.rent.clone();
+
+    let num_existing_addresses = LOOKUP_TABLE_MAX_ADDRESSES - MAX_EXTEND_ADDRESSES_PER_IX;
+    let max_data_len = LOOKUP_TABLE_META_SIZE + LOOKUP_TABLE_MAX_ADDRESSES * PUBKEY_BYTES;
+    let max_rent = rent.minimum_balance(max_data_len);
+
+    for table_lamports in [
+        rent.minimum_balance(LOOKUP_TABLE_META_SIZE + num_existing_addresses * PUBKEY_BYTES),
+        max_rent - 1,
+        max_rent,
+        max_rent + 1,
+    ] {
+        let lookup_table_address = Pubkey::new_unique();
+        let mut lookup_table_account = lookup_table_account(new_address_lookup_table(
+            Some(authority),
+            num_existing_addresses,
+        ));
+        lookup_table_account.set_lamports(table_lamports);
+
+        let mut new_addresses = Vec::with_capacity(MAX_EXTEND_ADDRESSES_PER_IX);
+        new_addresses.resize_with(MAX_EXTEND_ADDRESSES_PER_IX, Pubkey::new_unique);
+
+        let shortfall = max_rent.saturating_sub(table_lamports);
+
+        mollusk.process_and_validate_instruction(
+            &extend_lookup_table(lookup_table_address, authority, Some(payer), new_addresses),
+            &[
+                (lookup_table_address, lookup_table_account),
+                (authority, AccountSharedData::default()),
+                (
+                    payer,
+                    AccountSharedData::new(payer_lamports, 0, &system_program::id()),
+                ),
+                keyed_account_for_system_program(),
+            ],
+            &[
+                Check::success(),
+                Check::account(&lookup_table_address)
+                    .space(max_data_len)
+                    .lamports(table_lamports + shortfall)
+                    .build(),
+                Check::account(&payer)
+                    .lamports(payer_lamports - shortfall)
+                    .build(),
+            ],
+        );
+    }
+}
+
+#[test]
+fn test_extend_lookup_table_wrong_system_program() {
+    let mut mollusk = setup();
+    mollusk.warp_to_slot(1);
+
+    let payer = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let wrong_system_program = Pubkey::new_unique();
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(new_address_lookup_table(Some(authority), 0));
+
+    let mut instruction = extend_lookup_table(
+        lookup_table_address,
+        authority,
+        Some(payer),
+        vec![Pubkey::new_unique()],
+    );
+    instruction.accounts[3].pubkey = wrong_system_program;
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (
+                payer,
+                AccountSharedData::new(100_000_000, 0, &system_program::id()),
+            ),
+            (wrong_system_program, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::IncorrectProgramId)],
+    );
+}
+
 // Backwards compatibility test case.
 #[test]
 fn test_extend_readonly() {
@@ -393,3 +625,83 @@ fn test_extend_readonly() {
         ))],
     );
 }
+
+#[test]
+fn test_extend_lookup_table_failure_after_write() {
+    let mut mollusk = setup();
+    mollusk.warp_to_slot(1);
+
+    let payer = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(new_address_lookup_table(Some(authority), 0));
+
+    // The payer cannot cover the rent for the new address, so the transfer
+    // fails after the metadata has been overwritten and the account grown.
+    mollusk.process_and_validate_instruction(
+        &extend_lookup_table(
+            lookup_table_address,
+            authority,
+            Some(payer),
+            vec![Pubkey::new_unique()],
+        ),
+        &[
+            (lookup_table_address, lookup_table_account.clone()),
+            (authority, AccountSharedData::default()),
+            (payer, AccountSharedData::new(1, 0, &system_program::id())),
+            keyed_account_for_system_program(),
+        ],
+        &[
+            // `SystemError::ResultWithNegativeLamports`.
+            Check::err(ProgramError::Custom(1)),
+            Check::account(&lookup_table_address)
+                .lamports(lookup_table_account.lamports())
+                .data(lookup_table_account.data())
+                .build(),
+        ],
+    );
+}
+
+// Compute unit ceiling for extending a table with the maximum number of
+// addresses in a single instruction. Set with headroom above the
+// `extend_lookup_table_from_0_to_38` result in `benches/compute_units.md`.
+// When a change intentionally increases compute usage, re-run the benches
+// and raise this ceiling in the same change.
+const EXTEND_MAX_ADDRESSES_COMPUTE_UNIT_CEILING: u64 = 20_000;
+
+#[test]
+fn test_extend_lookup_table_compute_units() {
+    let mut mollusk = setup();
+    mollusk.warp_to_slot(1);
+
+    let payer = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(new_address_lookup_table(Some(authority), 0));
+
+    let mut new_addresses = Vec::with_capacity(MAX_EXTEND_ADDRESSES_PER_IX);
+    new_addresses.resize_with(MAX_EXTEND_ADDRESSES_PER_IX, Pubkey::new_unique);
+
+    let result = mollusk.process_instruction(
+        &extend_lookup_table(lookup_table_address, authority, Some(payer), new_addresses),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (
+                payer,
+                AccountSharedData::new(100_000_000, 0, &system_program::id()),
+            ),
+            keyed_account_for_system_program(),
+        ],
+    );
+
+    assert!(matches!(result.program_result, ProgramResult::Success));
+    assert!(
+        result.compute_units_consumed <= EXTEND_MAX_ADDRESSES_COMPUTE_UNIT_CEILING,
+        "Extend consumed {} compute units, exceeding the ceiling of {}",
+        result.compute_units_consumed,
+        EXTEND_MAX_ADDRESSES_COMPUTE_UNIT_CEILING,
+    );
+}
@@ -6,7 +6,7 @@ use {
     common::{lookup_table_account, new_address_lookup_table, setup},
     mollusk_svm::result::Check,
     solana_address_lookup_table_program::{
-        instruction::freeze_lookup_table, state::AddressLookupTable,
+        error::AddressLookupTableError, instruction::freeze_lookup_table, state::AddressLookupTable,
     },
     solana_sdk::{
         account::{AccountSharedData, ReadableAccount},
@@ -152,3 +152,30 @@ fn test_freeze_empty_lookup_table() {
         &[Check::err(ProgramError::InvalidInstructionData)],
     );
 }
+
+#[test]
+fn test_freeze_lookup_table_readonly() {
+    let mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+    let initialized_table = new_address_lookup_table(Some(authority), 10);
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    let mut instruction = freeze_lookup_table(lookup_table_address, authority);
+
+    // Make the lookup table account read-only.
+    instruction.accounts[0].is_writable = false;
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::Custom(
+            AddressLookupTableError::ReadonlyDataModified as u32,
+        ))],
+    );
+}
@@ -0,0 +1,213 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::{lookup_table_account, new_address_lookup_table, setup},
+    mollusk_svm::result::Check,
+    solana_address_lookup_table_program::{
+        error::AddressLookupTableError, instruction::reactivate_lookup_table,
+        state::AddressLookupTable,
+    },
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        clock::Slot,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        slot_hashes::MAX_ENTRIES,
+    },
+};
+
+#[test]
+fn test_reactivate_lookup_table() {
+    let mut mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+
+    for (deactivation_slot, current_slot) in [
+        (1, 1),           // Deactivated in the same slot
+        (1, 2),           // Deactivated one slot earlier
+        (1, 512),         // At the very edge of cooldown.
+        (512, 512 + 19),  // Arbitrary number within cooldown.
+        (512, 512 + 511), // At the very edge of cooldown.
+    ] {
+        mollusk.warp_to_slot(current_slot);
+
+        let mut initialized_table = {
+            let mut table = new_address_lookup_table(Some(authority), 10);
+            table.meta.deactivation_slot = deactivation_slot;
+            table
+        };
+
+        let lookup_table_address = Pubkey::new_unique();
+        let lookup_table_account = lookup_table_account(initialized_table.clone());
+
+        let result = mollusk.process_and_validate_instruction(
+            &reactivate_lookup_table(lookup_table_address, authority),
+            &[
+                (lookup_table_address, lookup_table_account),
+                (authority, AccountSharedData::default()),
+            ],
+            &[Check::success()],
+        );
+
+        let lookup_table_account = result.get_account(&lookup_table_address).unwrap();
+        let lookup_table = AddressLookupTable::deserialize(lookup_table_account.data()).unwrap();
+
+        assert_eq!(lookup_table.meta.deactivation_slot, Slot::MAX);
+
+        // Check that only the deactivation slot changed
+        initialized_table.meta.deactivation_slot = Slot::MAX;
+        assert_eq!(initialized_table, lookup_table);
+    }
+}
+
+#[test]
+fn test_reactivate_activated_lookup_table() {
+    let mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+    let initialized_table = new_address_lookup_table(Some(authority), 10);
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    mollusk.process_and_validate_instruction(
+        &reactivate_lookup_table(lookup_table_address, authority),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::InvalidArgument)],
+    );
+}
+
+#[test]
+fn test_reactivate_deactivated_lookup_table() {
+    // The cooldown period has expired, so the table can only be closed.
+    let mut mollusk = setup();
+    mollusk.warp_to_slot(MAX_ENTRIES as u64 + 1);
+
+    let authority = Pubkey::new_unique();
+    let initialized_table = {
+        let mut table = new_address_lookup_table(Some(authority), 10);
+        table.meta.deactivation_slot = 0;
+        table
+    };
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    mollusk.process_and_validate_instruction(
+        &reactivate_lookup_table(lookup_table_address, authority),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::InvalidArgument)],
+    );
+}
+
+#[test]
+fn test_reactivate_immutable_lookup_table() {
+    let mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+    let initialized_table = new_address_lookup_table(None, 10);
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    mollusk.process_and_validate_instruction(
+        &reactivate_lookup_table(lookup_table_address, authority),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::Immutable)],
+    );
+}
+
+#[test]
+fn test_reactivate_lookup_table_with_wrong_authority() {
+    let mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+    let wrong_authority = Pubkey::new_unique();
+    let initialized_table = {
+        let mut table = new_address_lookup_table(Some(authority), 10);
+        table.meta.deactivation_slot = 0;
+        table
+    };
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    mollusk.process_and_validate_instruction(
+        &reactivate_lookup_table(lookup_table_address, wrong_authority),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (wrong_authority, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::IncorrectAuthority)],
+    );
+}
+
+#[test]
+fn test_reactivate_lookup_table_without_signing() {
+    let mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+    let initialized_table = {
+        let mut table = new_address_lookup_table(Some(authority), 10);
+        table.meta.deactivation_slot = 0;
+        table
+    };
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    let mut instruction = reactivate_lookup_table(lookup_table_address, authority);
+    instruction.accounts[1].is_signer = false;
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::MissingRequiredSignature)],
+    );
+}
+
+#[test]
+fn test_reactivate_lookup_table_readonly() {
+    let mut mollusk = setup();
+    mollusk.warp_to_slot(2);
+
+    let authority = Pubkey::new_unique();
+    let initialized_table = {
+        let mut table = new_address_lookup_table(Some(authority), 10);
+        table.meta.deactivation_slot = 1;
+        table
+    };
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    let mut instruction = reactivate_lookup_table(lookup_table_address, authority);
+
+    // Make the lookup table account read-only.
+    instruction.accounts[0].is_writable = false;
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::Custom(
+            AddressLookupTableError::ReadonlyDataModified as u32,
+        ))],
+    );
+}
@@ -0,0 +1,172 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::{lookup_table_account, new_address_lookup_table, setup},
+    mollusk_svm::result::Check,
+    solana_address_lookup_table_program::{
+        error::AddressLookupTableError, instruction::transfer_authority, state::AddressLookupTable,
+    },
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+};
+
+#[test]
+fn test_transfer_authority() {
+    let mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+    let new_authority = Pubkey::new_unique();
+    let mut initialized_table = new_address_lookup_table(Some(authority), 10);
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table.clone());
+
+    let result = mollusk.process_and_validate_instruction(
+        &transfer_authority(lookup_table_address, authority, new_authority),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (new_authority, AccountSharedData::default()),
+        ],
+        &[Check::success()],
+    );
+
+    let lookup_table_account = result.get_account(&lookup_table_address).unwrap();
+    let lookup_table = AddressLookupTable::deserialize(lookup_table_account.data()).unwrap();
+
+    assert_eq!(lookup_table.meta.authority, Some(new_authority));
+
+    // Check that only the authority changed
+    initialized_table.meta.authority = Some(new_authority);
+    assert_eq!(initialized_table, lookup_table);
+}
+
+#[test]
+fn test_transfer_authority_immutable_lookup_table() {
+    let mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+    let new_authority = Pubkey::new_unique();
+    let initialized_table = new_address_lookup_table(None, 10);
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    mollusk.process_and_validate_instruction(
+        &transfer_authority(lookup_table_address, authority, new_authority),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (new_authority, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::Immutable)],
+    );
+}
+
+#[test]
+fn test_transfer_authority_deactivated_lookup_table() {
+    let mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+    let new_authority = Pubkey::new_unique();
+    let initialized_table = {
+        let mut table = new_address_lookup_table(Some(authority), 10);
+        table.meta.deactivation_slot = 0;
+        table
+    };
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    mollusk.process_and_validate_instruction(
+        &transfer_authority(lookup_table_address, authority, new_authority),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (new_authority, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::InvalidArgument)],
+    );
+}
+
+#[test]
+fn test_transfer_authority_with_wrong_authority() {
+    let mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+    let wrong_authority = Pubkey::new_unique();
+    let new_authority = Pubkey::new_unique();
+    let initialized_table = new_address_lookup_table(Some(authority), 10);
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    mollusk.process_and_validate_instruction(
+        &transfer_authority(lookup_table_address, wrong_authority, new_authority),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (wrong_authority, AccountSharedData::default()),
+            (new_authority, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::IncorrectAuthority)],
+    );
+}
+
+#[test]
+fn test_transfer_authority_without_signing() {
+    let mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+    let new_authority = Pubkey::new_unique();
+    let initialized_table = new_address_lookup_table(Some(authority), 10);
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    let mut instruction = transfer_authority(lookup_table_address, authority, new_authority);
+    instruction.accounts[1].is_signer = false;
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (new_authority, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::MissingRequiredSignature)],
+    );
+}
+
+#[test]
+fn test_transfer_authority_readonly() {
+    let mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+    let initialized_table = new_address_lookup_table(Some(authority), 10);
+    let new_authority = Pubkey::new_unique();
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    let mut instruction = transfer_authority(lookup_table_address, authority, new_authority);
+
+    // Make the lookup table account read-only.
+    instruction.accounts[0].is_writable = false;
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (new_authority, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::Custom(
+            AddressLookupTableError::ReadonlyDataModified as u32,
+        ))],
+    );
+}
@@ -0,0 +1,324 @@
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use {
+    common::{lookup_table_account, new_address_lookup_table, setup},
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check},
+    solana_address_lookup_table_program::{
+        instruction::{extend_lookup_table, trim_lookup_table},
+        state::{rent_for_addresses, AddressLookupTable, LOOKUP_TABLE_META_SIZE},
+    },
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        program_error::ProgramError,
+        pubkey::{Pubkey, PUBKEY_BYTES},
+        rent::Rent,
+        system_program,
+    },
+    std::borrow::Cow,
+};
+
+#[test]
+fn test_trim_lookup_table() {
+    let mut mollusk = setup();
+    mollusk.warp_to_slot(2);
+
+    let authority = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let initialized_table = {
+        let mut table = new_address_lookup_table(Some(authority), 10);
+        table.meta.last_extended_slot = 2;
+        table.meta.last_extended_slot_start_index = 4;
+        table
+    };
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table.clone());
+    let lookup_table_lamports = lookup_table_account.lamports();
+
+    let new_data_len = LOOKUP_TABLE_META_SIZE + 6 * PUBKEY_BYTES;
+    let new_rent_exempt_balance = rent_for_addresses(&Rent::default(), 6);
+
+    let result = mollusk.process_and_validate_instruction(
+        &trim_lookup_table(lookup_table_address, authority, recipient, 6),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (recipient, AccountSharedData::default()),
+        ],
+        &[
+            Check::success(),
+            Check::account(&lookup_table_address)
+                .lamports(new_rent_exempt_balance)
+                .build(),
+            Check::account(&recipient)
+                .lamports(lookup_table_lamports - new_rent_exempt_balance)
+                .build(),
+        ],
+    );
+
+    let lookup_table_account = result.get_account(&lookup_table_address).unwrap();
+    assert_eq!(lookup_table_account.data().len(), new_data_len);
+
+    let lookup_table = AddressLookupTable::deserialize(lookup_table_account.data()).unwrap();
+
+    // Only the trailing addresses are removed, the metadata is unchanged.
+    let expected_table = {
+        let mut table = initialized_table.clone();
+        table.addresses = Cow::Owned(initialized_table.addresses[..6].to_vec());
+        table
+    };
+    assert_eq!(lookup_table, expected_table);
+}
+
+#[test]
+fn test_trim_lookup_table_extended_in_earlier_slot() {
+    // Addresses appended in an earlier slot are active and cannot be
+    // trimmed.
+    let mut mollusk = setup();
+    mollusk.warp_to_slot(2);
+
+    let authority = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let initialized_table = {
+        let mut table = new_address_lookup_table(Some(authority), 10);
+        table.meta.last_extended_slot = 1;
+        table.meta.last_extended_slot_start_index = 8;
+        table
+    };
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    mollusk.process_and_validate_instruction(
+        &trim_lookup_table(lookup_table_address, authority, recipient, 8),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (recipient, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::InvalidArgument)],
+    );
+}
+
+#[test]
+fn test_trim_then_extend_cannot_replace_active_address() {
+    let mut mollusk = setup();
+    mollusk.warp_to_slot(2);
+
+    let authority = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let initialized_table = {
+        let mut table = new_address_lookup_table(Some(authority), 10);
+        table.meta.last_extended_slot = 1;
+        table
+    };
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table.clone());
+
+    // Trimming the active addresses at indices 6..10 is rejected, so their
+    // indices cannot be freed for reuse.
+    let result = mollusk.process_and_validate_instruction(
+        &trim_lookup_table(lookup_table_address, authority, recipient, 6),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (recipient, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::InvalidArgument)],
+    );
+    let lookup_table_account = result.get_account(&lookup_table_address).unwrap().clone();
+
+    // A following extension appends after the active addresses instead of
+    // replacing them.
+    let new_address = Pubkey::new_unique();
+    let result = mollusk.process_and_validate_instruction(
+        &extend_lookup_table(
+            lookup_table_address,
+            authority,
+            Some(payer),
+            vec![new_address],
+        ),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (
+                payer,
+                AccountSharedData::new(100_000_000, 0, &system_program::id()),
+            ),
+            keyed_account_for_system_program(),
+        ],
+        &[Check::success()],
+    );
+
+    let lookup_table_account = result.get_account(&lookup_table_address).unwrap();
+    let lookup_table = AddressLookupTable::deserialize(lookup_table_account.data()).unwrap();
+
+    assert_eq!(lookup_table.addresses.len(), 11);
+    assert_eq!(
+        lookup_table.addresses[..10],
+        initialized_table.addresses[..]
+    );
+    assert_eq!(lookup_table.addresses[10], new_address);
+    assert_eq!(lookup_table.meta.last_extended_slot_start_index, 10);
+}
+
+#[test]
+fn test_trim_lookup_table_pending_addresses() {
+    // Addresses appended in the current slot are not yet active, so they
+    // can be trimmed.
+    let mut mollusk = setup();
+    mollusk.warp_to_slot(2);
+
+    let authority = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let initialized_table = {
+        let mut table = new_address_lookup_table(Some(authority), 10);
+        table.meta.last_extended_slot = 2;
+        table.meta.last_extended_slot_start_index = 4;
+        table
+    };
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    let result = mollusk.process_and_validate_instruction(
+        &trim_lookup_table(lookup_table_address, authority, recipient, 4),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (recipient, AccountSharedData::default()),
+        ],
+        &[Check::success()],
+    );
+
+    let lookup_table_account = result.get_account(&lookup_table_address).unwrap();
+    let lookup_table = AddressLookupTable::deserialize(lookup_table_account.data()).unwrap();
+
+    assert_eq!(lookup_table.addresses.len(), 4);
+    assert_eq!(lookup_table.meta.last_extended_slot_start_index, 4);
+}
+
+#[test]
+fn test_trim_lookup_table_below_active_index() {
+    let mut mollusk = setup();
+    mollusk.warp_to_slot(2);
+
+    let authority = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let initialized_table = {
+        let mut table = new_address_lookup_table(Some(authority), 10);
+        table.meta.last_extended_slot = 2;
+        table.meta.last_extended_slot_start_index = 8;
+        table
+    };
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    mollusk.process_and_validate_instruction(
+        &trim_lookup_table(lookup_table_address, authority, recipient, 4),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (recipient, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::InvalidArgument)],
+    );
+}
+
+#[test]
+fn test_trim_lookup_table_to_current_length() {
+    let mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let initialized_table = new_address_lookup_table(Some(authority), 10);
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    mollusk.process_and_validate_instruction(
+        &trim_lookup_table(lookup_table_address, authority, recipient, 10),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (recipient, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::InvalidInstructionData)],
+    );
+}
+
+#[test]
+fn test_trim_immutable_lookup_table() {
+    let mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let initialized_table = new_address_lookup_table(None, 10);
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    mollusk.process_and_validate_instruction(
+        &trim_lookup_table(lookup_table_address, authority, recipient, 4),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (recipient, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::Immutable)],
+    );
+}
+
+#[test]
+fn test_trim_deactivated_lookup_table() {
+    let mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let initialized_table = {
+        let mut table = new_address_lookup_table(Some(authority), 10);
+        table.meta.deactivation_slot = 0;
+        table
+    };
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    mollusk.process_and_validate_instruction(
+        &trim_lookup_table(lookup_table_address, authority, recipient, 4),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (authority, AccountSharedData::default()),
+            (recipient, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::InvalidArgument)],
+    );
+}
+
+#[test]
+fn test_trim_lookup_table_with_wrong_authority() {
+    let mollusk = setup();
+
+    let authority = Pubkey::new_unique();
+    let wrong_authority = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let initialized_table = new_address_lookup_table(Some(authority), 10);
+
+    let lookup_table_address = Pubkey::new_unique();
+    let lookup_table_account = lookup_table_account(initialized_table);
+
+    mollusk.process_and_validate_instruction(
+        &trim_lookup_table(lookup_table_address, wrong_authority, recipient, 4),
+        &[
+            (lookup_table_address, lookup_table_account),
+            (wrong_authority, AccountSharedData::default()),
+            (recipient, AccountSharedData::default()),
+        ],
+        &[Check::err(ProgramError::IncorrectAuthority)],
+    );
+}
@@ -10,7 +10,11 @@ import {
 import './dump.mjs';
 
 // Configure arguments here.
-const testArgs = ['--features', 'bpf-entrypoint', ...cliArguments()];
+const testArgs = [
+  '--features',
+  'bpf-entrypoint,cli,serde-traits',
+  ...cliArguments(),
+];
 
 const hasSolfmt = await which('solfmt', { nothrow: true });
 